@@ -0,0 +1,256 @@
+//!Compile time BASE64 literals, decoded by [based64](https://docs.rs/based64)
+//!
+//!## Macros
+//!
+//!- `base64!("Zm9vYmFy")` - Decodes string literal into `&'static [u8; N]`;
+//!- `include_base64!("path")` - Decodes file, with path relative to `CARGO_MANIFEST_DIR`, into `&'static [u8; N]`.
+//!
+//!Both accept alphabet selector as first argument: `standard` (default) or `url`, e.g. `base64!(url, "-_8")`.
+//!
+//!Literals are decoded with `STANDARD_CODEC` or `URL_CODEC`, hence rules are the same as at runtime.
+//!Files are decoded with the same codec skipping whitespace, so they can contain line breaks.
+
+#![allow(clippy::style)]
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use based64::{Codec, STANDARD_CODEC, URL_CODEC};
+
+use std::path::PathBuf;
+
+struct Error {
+    message: String,
+    span: Span,
+}
+
+impl Error {
+    #[inline]
+    fn new<T: Into<String>>(message: T, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    ///Creates `compile_error!` invocation pointing to the span.
+    fn into_compile_error(self) -> TokenStream {
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+        let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+        args.set_span(self.span);
+
+        [
+            TokenTree::Ident(Ident::new("compile_error", self.span)),
+            TokenTree::Punct(bang),
+            TokenTree::Group(args),
+        ].iter().cloned().collect()
+    }
+}
+
+///Macro arguments: optional alphabet followed by string literal.
+struct Args {
+    codec: &'static Codec<'static>,
+    value: String,
+    span: Span,
+}
+
+fn parse_args(input: TokenStream) -> Result<Args, Error> {
+    let mut tokens = input.into_iter().peekable();
+    let mut codec = &STANDARD_CODEC;
+
+    if let Some(TokenTree::Ident(ident)) = tokens.peek() {
+        codec = match ident.to_string().as_str() {
+            "standard" => &STANDARD_CODEC,
+            "url" => &URL_CODEC,
+            _ => return Err(Error::new("Unknown alphabet, expected `standard` or `url`", ident.span())),
+        };
+        let span = ident.span();
+        tokens.next();
+
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => (),
+            Some(token) => return Err(Error::new("Expected `,` after alphabet", token.span())),
+            None => return Err(Error::new("Expected string literal after alphabet", span)),
+        }
+    }
+
+    let literal = match tokens.next() {
+        Some(TokenTree::Literal(literal)) => literal,
+        //Literal passed through macro_rules! is wrapped into group without delimiter
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+            let span = group.span();
+            match group.stream().into_iter().next() {
+                Some(TokenTree::Literal(literal)) => literal,
+                _ => return Err(Error::new("Expected string literal", span)),
+            }
+        },
+        Some(token) => return Err(Error::new("Expected string literal", token.span())),
+        None => return Err(Error::new("Expected string literal", Span::call_site())),
+    };
+    let span = literal.span();
+    let value = parse_string(&literal.to_string()).ok_or_else(|| Error::new("Expected string literal", span))?;
+
+    match tokens.next() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ',' && tokens.peek().is_none() => (),
+        Some(token) => return Err(Error::new("Unexpected token", token.span())),
+        None => (),
+    }
+
+    Ok(Args {
+        codec,
+        value,
+        span,
+    })
+}
+
+///Extracts value of string literal from its source, returning `None` if it is not string.
+fn parse_string(source: &str) -> Option<String> {
+    if let Some(raw) = source.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let value = raw.get(hashes..raw.len() - hashes)?;
+        return value.strip_prefix('"')?.strip_suffix('"').map(String::from);
+    }
+
+    let value = source.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            '\\' => result.push('\\'),
+            '"' => result.push('"'),
+            '\'' => result.push('\''),
+            'x' => {
+                let high = chars.next()?.to_digit(16)?;
+                let low = chars.next()?.to_digit(16)?;
+                result.push(char::from((high << 4 | low) as u8));
+            },
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let mut code = 0u32;
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        '_' => (),
+                        digit => code = code.checked_mul(16)? | digit.to_digit(16)?,
+                    }
+                }
+                result.push(char::from_u32(code)?);
+            },
+            //Line continuation skips following whitespace
+            '\n' | '\r' => while chars.peek().map_or(false, |ch| ch.is_whitespace()) {
+                chars.next();
+            },
+            _ => return None,
+        }
+    }
+
+    Some(result)
+}
+
+///Creates byte string literal, which is `&'static [u8; N]`.
+fn byte_string(data: &[u8], span: Span) -> TokenStream {
+    let mut literal = Literal::byte_string(data);
+    literal.set_span(span);
+    TokenTree::Literal(literal).into()
+}
+
+fn decode(codec: &Codec<'_>, data: &[u8], span: Span) -> Result<Vec<u8>, Error> {
+    codec.decode_into_vec(data).map_err(|error| Error::new(format!("Invalid BASE64: {}", error), span))
+}
+
+fn expand_base64(input: TokenStream) -> Result<TokenStream, Error> {
+    let args = parse_args(input)?;
+    let data = decode(args.codec, args.value.as_bytes(), args.span)?;
+    Ok(byte_string(&data, args.span))
+}
+
+fn expand_include_base64(input: TokenStream) -> Result<TokenStream, Error> {
+    let args = parse_args(input)?;
+    let path = match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => PathBuf::from(dir).join(&args.value),
+        None => PathBuf::from(&args.value),
+    };
+    let content = std::fs::read(&path).map_err(|error| Error::new(format!("Unable to read '{}': {}", path.display(), error), args.span))?;
+    let data = decode(&args.codec.with_skip_whitespace(true), &content, args.span)?;
+
+    //include_bytes! makes compiler track the file, so changes cause rebuild
+    let path = match path.to_str() {
+        Some(path) => path,
+        None => return Err(Error::new("Path must be valid UTF-8", args.span)),
+    };
+    let mut result = TokenStream::new();
+    result.extend(format!("const _: &[u8] = include_bytes!({:?});", path).parse::<TokenStream>());
+    result.extend(byte_string(&data, args.span));
+    Ok(TokenTree::Group(Group::new(Delimiter::Brace, result)).into())
+}
+
+#[proc_macro]
+///Decodes BASE64 string literal into `&'static [u8; N]`.
+///
+///Optional first argument selects alphabet: `standard` (default) or `url`.
+///
+///Invalid input results in compile error.
+///
+///# Errors
+///
+///Argument must be string literal:
+///
+///```compile_fail
+///let _ = based64_macros::base64!(42);
+///```
+///
+///Alphabet must be `standard` or `url`:
+///
+///```compile_fail
+///let _ = based64_macros::base64!(hex, "Zm9v");
+///```
+///
+///Literal must be valid BASE64:
+///
+///```compile_fail
+///let _ = based64_macros::base64!("Zm9v*");
+///```
+pub fn base64(input: TokenStream) -> TokenStream {
+    match expand_base64(input) {
+        Ok(result) => result,
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+#[proc_macro]
+///Decodes BASE64 file into `&'static [u8; N]`.
+///
+///Path is relative to `CARGO_MANIFEST_DIR` and whitespace within file is ignored.
+///Optional first argument selects alphabet: `standard` (default) or `url`.
+///
+///Unreadable file or invalid input results in compile error.
+///
+///# Errors
+///
+///File must exist:
+///
+///```compile_fail
+///let _ = based64_macros::include_base64!("tests/data/missing.b64");
+///```
+pub fn include_base64(input: TokenStream) -> TokenStream {
+    match expand_include_base64(input) {
+        Ok(result) => result,
+        Err(error) => error.into_compile_error(),
+    }
+}
@@ -0,0 +1,36 @@
+use based64_macros::{base64, include_base64};
+
+const FOOBAR: &[u8; 6] = base64!("Zm9vYmFy");
+static KEY: &[u8; 4] = base64!(url, "-_-_-w");
+
+macro_rules! decode_literal {
+    ($literal:literal) => {
+        base64!($literal)
+    };
+}
+
+#[test]
+fn should_decode_literal() {
+    assert_eq!(FOOBAR, b"foobar");
+    assert_eq!(KEY, &[0xfb, 0xff, 0xbf, 0xfb]);
+    assert_eq!(base64!(standard, "+/8="), &[0xfb, 0xff]);
+    assert_eq!(base64!(r"Zm9vYg=="), b"foob");
+    assert_eq!(base64!(r#"Zm9vYg"#,), b"foob");
+    assert_eq!(base64!(""), b"");
+    assert_eq!(decode_literal!("Zm9v"), b"foo");
+    assert_eq!(base64!("Zm9v\u{59}mFy"), b"foobar");
+    assert_eq!(base64!("Zm9v\x59\u{6_D}Fy"), b"foobar");
+}
+
+#[test]
+fn should_match_runtime_decoding() {
+    let input = "SGVsbG8sIHdvcmxkIQ";
+    assert_eq!(&base64!("SGVsbG8sIHdvcmxkIQ")[..], &based64::STANDARD_CODEC.decode_into_vec(input.as_bytes()).expect("to decode")[..]);
+}
+
+#[test]
+fn should_include_file() {
+    const DATA: &[u8; 9] = include_base64!("tests/data/foobarbaz.b64");
+    assert_eq!(DATA, b"foobarbaz");
+    assert_eq!(include_base64!(url, "tests/data/url.b64"), &[0xfb, 0xff, 0xbf, 0xfb]);
+}
@@ -0,0 +1,312 @@
+//! OpenPGP ASCII armor (RFC 4880, section 6)
+//!
+//!Requires feature `alloc`.
+//!
+//!Armored data is BASE64 followed by `=XXXX` line with CRC-24 of decoded data.
+//!Parser verifies checksum if it is present, accepts text around armor and ignores any whitespace within armored data.
+//!Cleartext signature framework (`-----BEGIN PGP SIGNED MESSAGE-----`) is not supported.
+
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{DecodeError, LineWrap, SKIP_WHITESPACE_CODEC, STANDARD_CODEC, PAD};
+use super::pem::{Lines, BEGIN, END, BOUNDARY_END, boundary_label};
+
+///Label of encrypted or signed message
+pub const MESSAGE: &str = "PGP MESSAGE";
+///Label of public key
+pub const PUBLIC_KEY_BLOCK: &str = "PGP PUBLIC KEY BLOCK";
+///Label of private key
+pub const PRIVATE_KEY_BLOCK: &str = "PGP PRIVATE KEY BLOCK";
+///Label of detached signature
+pub const SIGNATURE: &str = "PGP SIGNATURE";
+
+const CRC24_INIT: u32 = 0xb704ce;
+const CRC24_POLY: u32 = 0x1864cfb;
+const CRC24_TABLE: [u32; 256] = crc24_table();
+
+const fn crc24_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut idx = 0;
+    while idx < table.len() {
+        let mut crc = (idx as u32) << 16;
+        let mut bit = 0;
+        while bit < 8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+            bit += 1;
+        }
+        table[idx] = crc & 0xffffff;
+        idx += 1;
+    }
+    table
+}
+
+///Computes OpenPGP CRC-24 of `data`.
+pub const fn crc24(data: &[u8]) -> u32 {
+    let mut crc = CRC24_INIT;
+    let mut idx = 0;
+    while idx < data.len() {
+        crc = (crc << 8) ^ CRC24_TABLE[(((crc >> 16) as u8) ^ data[idx]) as usize];
+        idx += 1;
+    }
+    crc & 0xffffff
+}
+
+///Armor parsing error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArmorError {
+    ///Input contains no `-----BEGIN` line.
+    MissingBegin,
+    ///Line starting with `-----BEGIN` or `-----END` is malformed.
+    InvalidBoundary {
+        ///Position of the line within input.
+        offset: usize,
+    },
+    ///Armor is not terminated by `-----END` line.
+    MissingEnd {
+        ///Label of the armor.
+        label: String,
+    },
+    ///Label of `-----END` line differs from `-----BEGIN`.
+    LabelMismatch {
+        ///Label of `-----BEGIN` line.
+        begin: String,
+        ///Label of `-----END` line.
+        end: String,
+    },
+    ///Armor header is not in form of `Key: Value`, or headers are not followed by empty line.
+    InvalidHeader {
+        ///Position of the line within input.
+        offset: usize,
+    },
+    ///Checksum line is malformed or is not followed by `-----END` line.
+    InvalidChecksum {
+        ///Position of the line within input.
+        offset: usize,
+    },
+    ///Checksum does not match decoded data.
+    ChecksumMismatch {
+        ///Checksum written in armor.
+        expected: u32,
+        ///Checksum of decoded data.
+        actual: u32,
+    },
+    ///Data is not valid BASE64, with offset relative to the start of input.
+    Decode(DecodeError),
+}
+
+impl fmt::Display for ArmorError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArmorError::MissingBegin => fmt.write_str("Missing -----BEGIN line"),
+            ArmorError::InvalidBoundary { offset } => fmt.write_fmt(format_args!("Invalid armor header line at offset {}", offset)),
+            ArmorError::MissingEnd { label } => fmt.write_fmt(format_args!("Missing -----END line for '{}'", label)),
+            ArmorError::LabelMismatch { begin, end } => fmt.write_fmt(format_args!("Label mismatch: BEGIN '{}', END '{}'", begin, end)),
+            ArmorError::InvalidHeader { offset } => fmt.write_fmt(format_args!("Invalid armor header at offset {}", offset)),
+            ArmorError::InvalidChecksum { offset } => fmt.write_fmt(format_args!("Invalid checksum line at offset {}", offset)),
+            ArmorError::ChecksumMismatch { expected, actual } => fmt.write_fmt(format_args!("Checksum mismatch: expected 0x{:06x}, actual 0x{:06x}", expected, actual)),
+            ArmorError::Decode(error) => fmt.write_fmt(format_args!("Invalid data: {}", error)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArmorError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArmorError::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///OpenPGP armor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Armor {
+    ///Label, e.g. [MESSAGE](MESSAGE).
+    pub label: String,
+    ///Armor headers, e.g. `Version` or `Comment`, in order of appearance.
+    pub headers: Vec<(String, String)>,
+    ///Decoded data.
+    pub data: Vec<u8>,
+}
+
+impl Armor {
+    #[inline]
+    ///Creates new armor without headers.
+    pub fn new<L: Into<String>, D: Into<Vec<u8>>>(label: L, data: D) -> Self {
+        Self {
+            label: label.into(),
+            headers: Vec::new(),
+            data: data.into(),
+        }
+    }
+
+    #[inline]
+    ///Returns value of first header with `key`, compared case-insensitively.
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.iter().find(|(name, _)| name.eq_ignore_ascii_case(key)).map(|(_, value)| value.as_str())
+    }
+
+    #[inline]
+    ///Writes armor with checksum, wrapping data at 64 characters per line with `LF`.
+    ///
+    ///Label and headers are written as they are, so they must not contain line breaks.
+    pub fn encode(&self) -> String {
+        write(&self.label, &self.headers, &self.data)
+    }
+}
+
+fn write(label: &str, headers: &[(String, String)], data: &[u8]) -> String {
+    let codec = STANDARD_CODEC.with_line_wrap(Some(LineWrap::PEM));
+    let body = codec.encode_into_string(data);
+    let crc = crc24(data).to_be_bytes();
+    let mut checksum = [0u8; 4];
+    let size = STANDARD_CODEC.encode_to(&crc[1..], &mut checksum);
+    debug_assert_eq!(size, Some(4));
+
+    let mut result = String::with_capacity(body.len() + 2 * (label.len() + BEGIN.len() + BOUNDARY_END.len() + 1) + checksum.len() + 4);
+    result.push_str(BEGIN);
+    result.push_str(label);
+    result.push_str(BOUNDARY_END);
+    result.push('\n');
+
+    for (name, value) in headers {
+        result.push_str(name);
+        result.push_str(": ");
+        result.push_str(value);
+        result.push('\n');
+    }
+    //Empty line is required even without headers
+    result.push('\n');
+
+    if !body.is_empty() {
+        result.push_str(&body);
+        result.push('\n');
+    }
+    result.push(PAD as char);
+    result.extend(checksum.iter().map(|ch| *ch as char));
+    result.push('\n');
+
+    result.push_str(END);
+    result.push_str(label);
+    result.push_str(BOUNDARY_END);
+    result.push('\n');
+    result
+}
+
+///Parses armor headers, returning offset of data.
+fn parse_headers(lines: &mut Lines<'_>, headers: &mut Vec<(String, String)>) -> Result<usize, ArmorError> {
+    while let Some((offset, line)) = lines.next() {
+        if line.is_empty() {
+            return Ok(lines.offset);
+        }
+
+        let pos = match line.iter().position(|ch| *ch == b':') {
+            Some(pos) => pos,
+            None => return Err(ArmorError::InvalidHeader { offset }),
+        };
+        let name = String::from_utf8_lossy(&line[..pos]).into_owned();
+        //Line is trimmed, so space after empty value might be missing
+        let value = match line[pos + 1..].split_first() {
+            Some((b' ', value)) => value,
+            _ => &line[pos + 1..],
+        };
+        let value = String::from_utf8_lossy(value).trim().into();
+        headers.push((name, value));
+    }
+
+    Err(ArmorError::InvalidHeader { offset: lines.input.len() })
+}
+
+///Decodes `=XXXX` checksum line.
+fn parse_checksum(line: &[u8], offset: usize) -> Result<u32, ArmorError> {
+    let mut crc = [0u8; 4];
+    match line.len() == 5 && STANDARD_CODEC.decode_to(&line[1..], &mut crc[1..]) == Ok(3) {
+        true => Ok(u32::from_be_bytes(crc)),
+        false => Err(ArmorError::InvalidChecksum { offset }),
+    }
+}
+
+///Parses armor starting after `-----BEGIN` line.
+fn parse_armor(lines: &mut Lines<'_>, label: String) -> Result<Armor, ArmorError> {
+    let mut headers = Vec::new();
+    let data_offset = parse_headers(lines, &mut headers)?;
+    let mut checksum = None;
+
+    for (offset, line) in lines.by_ref() {
+        if line.starts_with(END.as_bytes()) {
+            let end = boundary_label(line, END).ok_or(ArmorError::InvalidBoundary { offset })?;
+            if end != label {
+                return Err(ArmorError::LabelMismatch {
+                    begin: label,
+                    end,
+                });
+            }
+
+            let data_end = checksum.map_or(offset, |(checksum_offset, _)| checksum_offset);
+            let data = SKIP_WHITESPACE_CODEC.decode_into_vec(&lines.input[data_offset..data_end]).map_err(|error| ArmorError::Decode(error.with_base_offset(data_offset)))?;
+
+            if let Some((_, expected)) = checksum {
+                let actual = crc24(&data);
+                if actual != expected {
+                    return Err(ArmorError::ChecksumMismatch {
+                        expected,
+                        actual,
+                    });
+                }
+            }
+
+            return Ok(Armor {
+                label,
+                headers,
+                data,
+            });
+        } else if let Some((checksum_offset, _)) = checksum {
+            //Checksum must be the last line
+            if !line.is_empty() {
+                return Err(ArmorError::InvalidChecksum { offset: checksum_offset });
+            }
+        } else if line.len() > 1 && line[0] == PAD && line[1] != PAD {
+            //Padding can be wrapped to its own line, but it consists of padding characters only
+            checksum = Some((offset, parse_checksum(line, offset)?));
+        }
+    }
+
+    Err(ArmorError::MissingEnd {
+        label,
+    })
+}
+
+///Parses first armor in `input`, ignoring any text around it.
+///
+///Checksum is verified if present.
+pub fn parse(input: &[u8]) -> Result<Armor, ArmorError> {
+    let mut lines = Lines {
+        input,
+        offset: 0,
+    };
+
+    while let Some((offset, line)) = lines.next() {
+        if line.starts_with(BEGIN.as_bytes()) {
+            let label = boundary_label(line, BEGIN).ok_or(ArmorError::InvalidBoundary { offset })?;
+            return parse_armor(&mut lines, label);
+        }
+    }
+
+    Err(ArmorError::MissingBegin)
+}
+
+#[inline]
+///Writes armor with checksum and without headers.
+///
+///Label must not contain line breaks.
+pub fn encode(label: &str, data: &[u8]) -> String {
+    write(label, &[], data)
+}
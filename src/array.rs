@@ -0,0 +1,206 @@
+//! Const functions working with fixed size arrays
+
+use super::{Codec, DecodeError, Padding, PAD};
+use super::raw::{is_padded_chunk, decode_quad, decode_char, strict_tail_len, strict_tail_data_len, check_trailing_bits};
+
+impl<'a> Codec<'a> {
+    ///Encodes array, following padding policy and line wrapping.
+    ///
+    ///Intended for compile time literals, at runtime prefer [encode_to](Codec::encode_to).
+    ///
+    ///# Panics
+    ///
+    ///If `M` is not equal to [encode_len](Codec::encode_len) of `N`. When used in const context it results in compile time error.
+    pub const fn encode_array<const N: usize, const M: usize>(&self, src: &[u8; N]) -> [u8; M] {
+        if self.encode_len(N) != M {
+            panic!("Output size must be equal to encoded length");
+        }
+
+        let is_padded = match self.padding {
+            Padding::Forbidden => false,
+            _ => true,
+        };
+        let mut result = [0u8; M];
+        let mut len = 0;
+        let mut column = 0;
+
+        let mut idx = 0;
+        while idx < N {
+            let rest = N - idx;
+            let group = [
+                src[idx],
+                if rest > 1 { src[idx + 1] } else { 0 },
+                if rest > 2 { src[idx + 2] } else { 0 },
+            ];
+            let mut chars = [
+                self.table[(group[0] >> 2) as usize],
+                self.table[((group[0] & 0x3) << 4 | group[1] >> 4) as usize],
+                self.table[((group[1] & 0xf) << 2 | group[2] >> 6) as usize],
+                self.table[(group[2] & 0x3f) as usize],
+            ];
+            let mut chars_len = if rest > 2 { 4 } else { rest + 1 };
+            if is_padded {
+                while chars_len < 4 {
+                    chars[chars_len] = PAD;
+                    chars_len += 1;
+                }
+            }
+
+            let mut char_idx = 0;
+            while char_idx < chars_len {
+                if let Some(line_wrap) = self.line_wrap {
+                    if column == line_wrap.width {
+                        let ending = line_wrap.ending.as_bytes();
+                        let mut ending_idx = 0;
+                        while ending_idx < ending.len() {
+                            result[len] = ending[ending_idx];
+                            len += 1;
+                            ending_idx += 1;
+                        }
+                        column = 0;
+                    }
+                }
+
+                result[len] = chars[char_idx];
+                len += 1;
+                column += 1;
+                char_idx += 1;
+            }
+
+            idx += 3;
+        }
+
+        result
+    }
+
+    ///Decodes array, following rules of the codec.
+    ///
+    ///Intended for compile time literals, at runtime prefer [decode_to](Codec::decode_to).
+    ///
+    ///# Panics
+    ///
+    ///If input is invalid or `M` is not equal to decoded length. When used in const context it results in compile time error.
+    pub const fn decode_array<const N: usize, const M: usize>(&self, src: &[u8; N]) -> [u8; M] {
+        match self.decode_array_inner(src) {
+            Ok((result, len)) => match len == M {
+                true => result,
+                false => panic!("Output size must be equal to decoded length"),
+            },
+            Err(error) => panic!("{}", error.as_str()),
+        }
+    }
+
+    ///Decodes into `M` bytes, returning them with total decoded length, that might exceed `M`.
+    const fn decode_array_inner<const N: usize, const M: usize>(&self, src: &[u8; N]) -> Result<([u8; M], usize), DecodeError> {
+        //Input without skipped characters, with their original offsets
+        let mut chars = [0u8; N];
+        let mut offsets = [0usize; N];
+        let mut chars_len = 0;
+
+        let mut idx = 0;
+        while idx < N {
+            if !self.is_skipped(src[idx]) {
+                chars[chars_len] = src[idx];
+                offsets[chars_len] = idx;
+                chars_len += 1;
+            }
+            idx += 1;
+        }
+
+        let chars = chars.split_at(chars_len).0;
+        let mut result = [0u8; M];
+        let mut len = 0;
+
+        //Strict mode decodes complete groups before last one, that can contain padding
+        let body_len = match self.strict {
+            true => chars_len - strict_tail_len(chars_len),
+            false => chars_len,
+        };
+
+        let mut pos = 0;
+        while pos < body_len {
+            let mut quad = [0u8; 4];
+            let mut quad_len = 0;
+            let mut is_end = false;
+
+            while quad_len < 4 {
+                if pos + quad_len >= body_len {
+                    if quad_len != 0 && matches!(self.padding, Padding::Required) {
+                        return Err(DecodeError::InvalidPadding);
+                    }
+                    is_end = true;
+                    break;
+                }
+
+                let ch = chars[pos + quad_len];
+                //Strict mode reports padding in body as error
+                if ch == PAD && !self.strict {
+                    match self.padding {
+                        Padding::Forbidden => return Err(DecodeError::InvalidPadding),
+                        Padding::Required => if !is_padded_chunk(chars.split_at(pos).1, quad_len) {
+                            return Err(DecodeError::InvalidPadding);
+                        },
+                        Padding::Indifferent => (),
+                    }
+                    is_end = true;
+                    break;
+                }
+
+                quad[quad_len] = match decode_char(&self.reverse, ch, offsets[pos + quad_len]) {
+                    Ok(symbol) => symbol,
+                    Err(error) => return Err(error),
+                };
+                quad_len += 1;
+            }
+
+            let bytes = decode_quad(quad);
+            let mut byte_idx = 0;
+            while byte_idx + 1 < quad_len {
+                if len < M {
+                    result[len] = bytes[byte_idx];
+                }
+                len += 1;
+                byte_idx += 1;
+            }
+
+            if is_end {
+                return Ok((result, len));
+            }
+            pos += 4;
+        }
+
+        if chars_len > body_len {
+            let tail = chars.split_at(body_len).1;
+            let data_len = match strict_tail_data_len(tail, self.padding) {
+                Ok(data_len) => data_len,
+                Err(error) => return Err(error),
+            };
+
+            let mut quad = [0u8; 4];
+            let mut quad_len = 0;
+            while quad_len < data_len {
+                quad[quad_len] = match decode_char(&self.reverse, tail[quad_len], offsets[body_len + quad_len]) {
+                    Ok(symbol) => symbol,
+                    Err(error) => return Err(error),
+                };
+                quad_len += 1;
+            }
+
+            if let Err(error) = check_trailing_bits(&quad, data_len) {
+                return Err(error);
+            }
+
+            let bytes = decode_quad(quad);
+            let mut byte_idx = 0;
+            while byte_idx + 1 < data_len {
+                if len < M {
+                    result[len] = bytes[byte_idx];
+                }
+                len += 1;
+                byte_idx += 1;
+            }
+        }
+
+        Ok((result, len))
+    }
+}
@@ -0,0 +1,443 @@
+//! Ascii85 codec, with Adobe and btoa variants
+//!
+//!Every 4 bytes of input are encoded as 5 characters from `!` to `u`, with last group truncated.
+//!
+//!Built-in variants:
+//!
+//!- [ADOBE_CODEC](ADOBE_CODEC) - Used by PostScript and PDF. Output is enclosed by `<~` and `~>`, with `z` for group of zeros;
+//!- [BTOA_CODEC](BTOA_CODEC) - Used by `btoa`. Uses `z` for group of zeros and `y` for group of spaces.
+//!
+//!Decoding skips ASCII whitespace anywhere within data.
+//!Group, that does not fit 32 bits, is reported as [InvalidByte](DecodeError::InvalidByte) at its last character.
+
+use super::DecodeError;
+
+///Adobe's codec, with `<~ ~>` delimiters and `z` compression.
+pub static ADOBE_CODEC: Codec = Codec::new().with_delimiters(true).with_zero_compression(true);
+///btoa's codec, with `z` and `y` compression.
+pub static BTOA_CODEC: Codec = Codec::new().with_zero_compression(true).with_space_compression(true);
+
+const DIGIT_BASE: u8 = b'!';
+const DIGIT_MAX: u8 = b'u';
+///Group of zeros
+const ZERO_GROUP: u8 = b'z';
+///Group of spaces
+const SPACE_GROUP: u8 = b'y';
+const SPACE_GROUP_VALUE: u32 = 0x20202020;
+const START: &[u8; 2] = b"<~";
+const END: &[u8; 2] = b"~>";
+
+#[inline(always)]
+///Returns maximum number of bytes necessary to encode input of provided size, without delimiters.
+///
+///Exact unless compression is used.
+///
+///On overflow returns wrapped value.
+pub const fn encode_len(input: usize) -> usize {
+    let tail = input % 4;
+    (input / 4).wrapping_mul(5).wrapping_add(tail + (tail != 0) as usize)
+}
+
+#[inline]
+///Returns number of bytes necessary to decode provided input without delimiters, accepting both `z` and `y` groups.
+///
+///Whitespace is not counted, any other character is counted as part of data.
+pub fn decode_len(input: &[u8]) -> usize {
+    data_decode_len(input, true, true)
+}
+
+fn data_decode_len(input: &[u8], zero_compression: bool, space_compression: bool) -> usize {
+    let mut len = 0usize;
+    let mut group_len = 0;
+    for ch in input.iter().copied() {
+        if ch.is_ascii_whitespace() {
+            continue;
+        } else if group_len == 0 && ((ch == ZERO_GROUP && zero_compression) || (ch == SPACE_GROUP && space_compression)) {
+            len += 4;
+            continue;
+        }
+
+        group_len += 1;
+        if group_len == 5 {
+            len += 4;
+            group_len = 0;
+        }
+    }
+
+    match group_len {
+        0 => len,
+        group_len => len + group_len - 1,
+    }
+}
+
+#[inline(always)]
+fn encode_group(value: u32) -> [u8; 5] {
+    let mut digits = [0u8; 5];
+    let mut value = value;
+    let mut idx = digits.len();
+    while idx > 0 {
+        idx -= 1;
+        digits[idx] = DIGIT_BASE + (value % 85) as u8;
+        value /= 85;
+    }
+    digits
+}
+
+///Ascii85 codec
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Codec {
+    delimiters: bool,
+    zero_compression: bool,
+    space_compression: bool,
+}
+
+impl Codec {
+    #[inline(always)]
+    ///Creates new codec without delimiters and compression.
+    pub const fn new() -> Self {
+        Self {
+            delimiters: false,
+            zero_compression: false,
+            space_compression: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Sets whether data is enclosed by `<~` and `~>`, used by both encoding and decoding.
+    ///
+    ///Decoding allows whitespace around delimiters, while anything else outside of them is invalid.
+    ///
+    ///Default is `false`.
+    pub const fn with_delimiters(mut self, delimiters: bool) -> Self {
+        self.delimiters = delimiters;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets whether group of 4 zero bytes is written as `z`, used by both encoding and decoding.
+    ///
+    ///Default is `false`.
+    pub const fn with_zero_compression(mut self, zero_compression: bool) -> Self {
+        self.zero_compression = zero_compression;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets whether group of 4 spaces is written as `y`, used by both encoding and decoding.
+    ///
+    ///Default is `false`.
+    pub const fn with_space_compression(mut self, space_compression: bool) -> Self {
+        self.space_compression = space_compression;
+        self
+    }
+
+    #[inline(always)]
+    ///Returns maximum number of bytes necessary to encode input of provided size, including delimiters.
+    ///
+    ///Exact unless compression is used.
+    ///
+    ///On overflow returns wrapped value.
+    pub const fn encode_len(&self, input: usize) -> usize {
+        match self.delimiters {
+            true => encode_len(input).wrapping_add(START.len() + END.len()),
+            false => encode_len(input),
+        }
+    }
+
+    ///Returns number of bytes necessary to decode provided input.
+    ///
+    ///Whitespace and delimiters are not counted, any other character is counted as part of data.
+    ///Returns 0 if delimiters are invalid.
+    pub fn decode_len(&self, input: &[u8]) -> usize {
+        match self.strip_delimiters(input) {
+            Ok((data, _)) => data_decode_len(data, self.zero_compression, self.space_compression),
+            Err(_) => 0,
+        }
+    }
+
+    ///Returns data within delimiters, if required, and its offset.
+    fn strip_delimiters<'b>(&self, input: &'b [u8]) -> Result<(&'b [u8], usize), DecodeError> {
+        if !self.delimiters {
+            return Ok((input, 0));
+        }
+
+        let start = input.iter().position(|ch| !ch.is_ascii_whitespace()).unwrap_or(input.len());
+        let end = input.iter().rposition(|ch| !ch.is_ascii_whitespace()).map_or(start, |pos| pos + 1);
+        let trimmed = &input[start..end];
+
+        for (idx, expected) in START.iter().enumerate() {
+            match trimmed.get(idx) {
+                Some(byte) if byte == expected => (),
+                Some(byte) => return Err(DecodeError::InvalidByte {
+                    offset: start + idx,
+                    byte: *byte,
+                }),
+                None => return Err(DecodeError::InvalidLength),
+            }
+        }
+
+        let data_start = start + START.len();
+        let data_end = match input[data_start..end].iter().position(|ch| *ch == END[0]) {
+            Some(pos) => data_start + pos,
+            None => return Err(DecodeError::InvalidLength),
+        };
+        match input[data_end + 1..end].first() {
+            Some(byte) if *byte == END[1] => (),
+            Some(byte) => return Err(DecodeError::InvalidByte {
+                offset: data_end + 1,
+                byte: *byte,
+            }),
+            None => return Err(DecodeError::InvalidLength),
+        }
+        if data_end + END.len() < end {
+            return Err(DecodeError::InvalidByte {
+                offset: data_end + END.len(),
+                byte: input[data_end + END.len()],
+            });
+        }
+
+        Ok((&input[data_start..data_end], data_start))
+    }
+
+    ///Encodes input, that must fit `dst`, returning number of written bytes.
+    unsafe fn encode_inner(&self, src: &[u8], dst: *mut u8) -> usize {
+        let mut cursor = dst;
+        macro_rules! write {
+            ($bytes:expr) => {{
+                let bytes: &[u8] = $bytes;
+                core::ptr::copy_nonoverlapping(bytes.as_ptr(), cursor, bytes.len());
+                cursor = cursor.add(bytes.len());
+            }};
+        }
+
+        if self.delimiters {
+            write!(START);
+        }
+
+        let mut chunks = src.chunks_exact(4);
+        for chunk in chunks.by_ref() {
+            let value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            if value == 0 && self.zero_compression {
+                write!(&[ZERO_GROUP]);
+            } else if value == SPACE_GROUP_VALUE && self.space_compression {
+                write!(&[SPACE_GROUP]);
+            } else {
+                write!(&encode_group(value));
+            }
+        }
+
+        let tail = chunks.remainder();
+        if !tail.is_empty() {
+            let mut group = [0u8; 4];
+            group[..tail.len()].copy_from_slice(tail);
+            write!(&encode_group(u32::from_be_bytes(group))[..tail.len() + 1]);
+        }
+
+        if self.delimiters {
+            write!(END);
+        }
+
+        cursor as usize - dst as usize
+    }
+
+    ///Decodes input, that must fit `dst`, returning number of written bytes.
+    unsafe fn decode_inner(&self, src: &[u8], dst: *mut u8) -> Result<usize, DecodeError> {
+        let (data, base_offset) = self.strip_delimiters(src)?;
+
+        let mut written = 0;
+        let mut value = 0u64;
+        let mut group_len = 0;
+        let mut last_offset = 0;
+        for (offset, ch) in data.iter().copied().enumerate() {
+            let offset = base_offset + offset;
+            if ch.is_ascii_whitespace() {
+                continue;
+            } else if group_len == 0 && ch == ZERO_GROUP && self.zero_compression {
+                core::ptr::write_bytes(dst.add(written), 0, 4);
+                written += 4;
+                continue;
+            } else if group_len == 0 && ch == SPACE_GROUP && self.space_compression {
+                core::ptr::copy_nonoverlapping(SPACE_GROUP_VALUE.to_be_bytes().as_ptr(), dst.add(written), 4);
+                written += 4;
+                continue;
+            } else if !(DIGIT_BASE..=DIGIT_MAX).contains(&ch) {
+                return Err(DecodeError::InvalidByte {
+                    offset,
+                    byte: ch,
+                });
+            }
+
+            value = value * 85 + (ch - DIGIT_BASE) as u64;
+            group_len += 1;
+            last_offset = offset;
+            if group_len == 5 {
+                if value > u32::MAX as u64 {
+                    return Err(DecodeError::InvalidByte {
+                        offset,
+                        byte: ch,
+                    });
+                }
+                core::ptr::copy_nonoverlapping((value as u32).to_be_bytes().as_ptr(), dst.add(written), 4);
+                written += 4;
+                value = 0;
+                group_len = 0;
+            }
+        }
+
+        match group_len {
+            0 => (),
+            1 => return Err(DecodeError::InvalidLength),
+            _ => {
+                for _ in group_len..5 {
+                    value = value * 85 + (DIGIT_MAX - DIGIT_BASE) as u64;
+                }
+                if value > u32::MAX as u64 {
+                    return Err(DecodeError::InvalidByte {
+                        offset: last_offset,
+                        byte: src[last_offset],
+                    });
+                }
+                core::ptr::copy_nonoverlapping((value as u32).to_be_bytes().as_ptr(), dst.add(written), group_len - 1);
+                written += group_len - 1;
+            }
+        }
+
+        Ok(written)
+    }
+
+    ///Encoding function writing to slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to encode;
+    ///- `dst` - Output to write, must fit [encode_len](Codec::encode_len) bytes;
+    ///
+    ///# Result
+    ///
+    ///Returns `Some` if successful, containing number of bytes written.
+    ///
+    ///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
+    #[inline]
+    pub fn encode_to(&self, src: &[u8], dst: &mut [u8]) -> Option<usize> {
+        let required_len = self.encode_len(src.len());
+        if required_len < src.len() || required_len > dst.len() {
+            return None;
+        }
+
+        Some(unsafe {
+            self.encode_inner(src, dst.as_mut_ptr())
+        })
+    }
+
+    ///Decoding function writing to slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to decode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///Returns `Ok` if successful, containing number of bytes written.
+    ///
+    ///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
+    #[inline]
+    pub fn decode_to(&self, src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
+        let required_len = self.decode_len(src);
+        if required_len > dst.len() {
+            return Err(DecodeError::OutputTooSmall {
+                required: required_len,
+            });
+        }
+
+        unsafe {
+            self.decode_inner(src, dst.as_mut_ptr())
+        }
+    }
+}
+
+impl Default for Codec {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impl {
+    extern crate alloc;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use super::{Codec, DecodeError};
+
+    impl Codec {
+        ///Encoding function returns vector with data written.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///- `src` - Input to encode;
+        ///
+        ///# Panics
+        ///
+        ///In case of required size to be too big
+        #[inline]
+        pub fn encode_into_vec(&self, src: &[u8]) -> Vec<u8> {
+            let required_len = self.encode_len(src.len());
+            //>= for case of zero sized input
+            assert!(required_len >= src.len());
+
+            let mut result = Vec::with_capacity(required_len);
+            unsafe {
+                let len = self.encode_inner(src, result.as_mut_ptr());
+                result.set_len(len);
+            }
+
+            result
+        }
+
+        ///Encoding function returns string.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///- `src` - Input to encode;
+        ///
+        ///# Panics
+        ///
+        ///In case of required size to be too big
+        #[inline]
+        pub fn encode_into_string(&self, src: &[u8]) -> String {
+            let result = self.encode_into_vec(src);
+
+            //Output contains only ASCII characters
+            unsafe {
+                String::from_utf8_unchecked(result)
+            }
+        }
+
+        ///Decoding function returns vector with data written.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///
+        ///- `src` - Input to decode;
+        ///
+        ///# Result
+        ///
+        ///Returns `Ok` if successful, containing decoded output
+        ///
+        ///Returns `Err` if `src` is invalid input.
+        #[inline]
+        pub fn decode_into_vec(&self, src: &[u8]) -> Result<Vec<u8>, DecodeError> {
+            let mut result = Vec::with_capacity(self.decode_len(src));
+            unsafe {
+                let len = self.decode_inner(src, result.as_mut_ptr())?;
+                result.set_len(len);
+            }
+
+            Ok(result)
+        }
+    }
+}
@@ -0,0 +1,475 @@
+//! BASE16 (hex) codec
+//!
+//!Every byte is encoded as 2 characters, optionally separated by [Separator](Separator), e.g. `AB:CD:EF` for fingerprints.
+//!
+//!Decoding accepts characters of both cases regardless of [Case](Case).
+
+use core::mem;
+use core::ptr::NonNull;
+
+use super::{swar, DecodeError, REVERSE_TABLE_SIZE};
+
+///Upper case alphabet
+pub static UPPER_TABLE: &[u8; 16] = b"0123456789ABCDEF";
+///Lower case alphabet
+pub static LOWER_TABLE: &[u8; 16] = b"0123456789abcdef";
+///Codec which encodes with `UPPER_TABLE` without separator
+pub static UPPER_CODEC: Codec = Codec::new(Case::Upper);
+///Codec which encodes with `LOWER_TABLE` without separator
+pub static LOWER_CODEC: Codec = Codec::new(Case::Lower);
+
+static REVERSE_TABLE: [i8; REVERSE_TABLE_SIZE] = build_reverse_table();
+
+const fn build_reverse_table() -> [i8; REVERSE_TABLE_SIZE] {
+    let mut reverse = [-1i8; REVERSE_TABLE_SIZE];
+
+    let mut idx = 0;
+    while idx < UPPER_TABLE.len() {
+        reverse[UPPER_TABLE[idx] as usize] = idx as i8;
+        reverse[LOWER_TABLE[idx] as usize] = idx as i8;
+        idx += 1;
+    }
+
+    reverse
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to encode input of provided size, without separator.
+///
+///On overflow returns wrapped value.
+pub const fn encode_len(input: usize) -> usize {
+    input.wrapping_mul(2)
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to decode provided input, without separator.
+///
+///For input of odd length it is rounded down.
+pub const fn decode_len(input: &[u8]) -> usize {
+    input.len() / 2
+}
+
+///Case of encoded letters
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Case {
+    ///`A` to `F`
+    Upper,
+    ///`a` to `f`
+    Lower,
+}
+
+impl Case {
+    #[inline(always)]
+    ///Returns character table of case.
+    pub const fn table(&self) -> &'static [u8; 16] {
+        match self {
+            Case::Upper => UPPER_TABLE,
+            Case::Lower => LOWER_TABLE,
+        }
+    }
+}
+
+///Separator between encoded bytes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Separator {
+    ///`:`
+    Colon,
+    ///` `
+    Space,
+}
+
+impl Separator {
+    #[inline(always)]
+    ///Returns character of separator.
+    pub const fn as_byte(&self) -> u8 {
+        match self {
+            Separator::Colon => b':',
+            Separator::Space => b' ',
+        }
+    }
+}
+
+///BASE16 codec
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Codec {
+    case: Case,
+    separator: Option<Separator>,
+}
+
+impl Codec {
+    #[inline(always)]
+    ///Creates new codec, encoding letters in provided case.
+    pub const fn new(case: Case) -> Self {
+        Self {
+            case,
+            separator: None,
+        }
+    }
+
+    #[inline(always)]
+    ///Sets separator between bytes, used by both encoding and decoding.
+    ///
+    ///When set, decoding requires exactly one separator between every pair of characters.
+    ///
+    ///Default is `None`.
+    pub const fn with_separator(mut self, separator: Option<Separator>) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes necessary to encode input of provided size, including separators.
+    ///
+    ///On overflow returns wrapped value.
+    pub const fn encode_len(&self, input: usize) -> usize {
+        match self.separator {
+            Some(_) if input > 0 => input.wrapping_mul(3).wrapping_sub(1),
+            _ => encode_len(input),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes necessary to decode provided input.
+    ///
+    ///For input with invalid length it is rounded down.
+    pub const fn decode_len(&self, input: &[u8]) -> usize {
+        match self.separator {
+            Some(_) => (input.len() + 1) / 3,
+            None => decode_len(input),
+        }
+    }
+
+    ///Encodes input, that must fit `dst`, returning number of written bytes.
+    unsafe fn encode_inner(&self, src: &[u8], dst: *mut u8) -> usize {
+        let table = self.case.table();
+
+        let separator = match self.separator {
+            Some(separator) => separator.as_byte(),
+            None => {
+                let consumed = encode_bulk(table, src, dst);
+                for (idx, byte) in src[consumed..].iter().enumerate() {
+                    *dst.add(2 * (consumed + idx)) = table[(byte >> 4) as usize];
+                    *dst.add(2 * (consumed + idx) + 1) = table[(byte & 0xf) as usize];
+                }
+                return encode_len(src.len());
+            }
+        };
+
+        let mut cursor = dst;
+        for (idx, byte) in src.iter().enumerate() {
+            if idx > 0 {
+                *cursor = separator;
+                cursor = cursor.add(1);
+            }
+            *cursor = table[(byte >> 4) as usize];
+            *cursor.add(1) = table[(byte & 0xf) as usize];
+            cursor = cursor.add(2);
+        }
+
+        cursor as usize - dst as usize
+    }
+
+    ///Decodes input, that must fit `dst`, returning number of written bytes.
+    unsafe fn decode_inner(&self, src: &[u8], dst: *mut u8) -> Result<usize, DecodeError> {
+        #[inline(always)]
+        fn value(src: &[u8], offset: usize) -> Result<u8, DecodeError> {
+            let byte = src[offset];
+            match REVERSE_TABLE[byte as usize] {
+                -1 => Err(DecodeError::InvalidByte {
+                    offset,
+                    byte,
+                }),
+                value => Ok(value as u8),
+            }
+        }
+
+        let (separator, step, consumed) = match self.separator {
+            Some(separator) => (Some(separator.as_byte()), 3, 0),
+            None => (None, 2, decode_bulk(src, dst)),
+        };
+
+        let mut offset = consumed;
+        let mut written = consumed / 2;
+        while offset < src.len() {
+            if src.len() - offset == 1 {
+                value(src, offset)?;
+                return Err(DecodeError::InvalidLength);
+            }
+
+            *dst.add(written) = value(src, offset)? << 4 | value(src, offset + 1)?;
+            written += 1;
+
+            if let Some(separator) = separator {
+                match src.get(offset + 2) {
+                    None => (),
+                    Some(byte) if *byte == separator && offset + 3 < src.len() => (),
+                    Some(byte) if *byte == separator => return Err(DecodeError::InvalidLength),
+                    Some(byte) => return Err(DecodeError::InvalidByte {
+                        offset: offset + 2,
+                        byte: *byte,
+                    }),
+                }
+            }
+            offset += step;
+        }
+
+        Ok(written)
+    }
+
+    #[inline]
+    ///Raw encoding function.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to encode;
+    ///- `dst` - Output to write;
+    ///- `len` - Output length, modified with required size regardless of outcome, unless calculation wrapping happens.
+    ///
+    ///# Result
+    ///Returns `true` on success.
+    ///
+    ///Returns `false` if buffer overflow would to happen or required_len is too big.
+    ///
+    ///# Safety
+    ///
+    ///`dst` must be valid for writes of `len` bytes.
+    pub unsafe fn encode_to_raw(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
+        let required_len = self.encode_len(src.len());
+        if required_len < src.len() {
+            return false;
+        }
+
+        let is_fit = required_len <= *len;
+        if is_fit {
+            self.encode_inner(src, dst.as_ptr());
+        }
+        *len = required_len;
+        is_fit
+    }
+
+    #[inline]
+    ///Raw decoding function.
+    ///
+    ///# Arguments
+    ///- `src` - Input to decode;
+    ///- `dst` - Output to write;
+    ///- `len` - Output length, modified with number of bytes written on success or with required size if it is insufficient.
+    ///
+    ///# Result
+    ///Returns `Ok` on success.
+    ///
+    ///Returns `Err` if buffer overflow would to happen or `src` is invalid hex.
+    ///
+    ///# Safety
+    ///
+    ///`dst` must be valid for writes of `len` bytes.
+    pub unsafe fn decode_to_raw(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
+        let required_len = self.decode_len(src);
+        if required_len > *len {
+            *len = required_len;
+            return Err(DecodeError::OutputTooSmall {
+                required: required_len,
+            });
+        }
+
+        *len = self.decode_inner(src, dst.as_ptr())?;
+        Ok(())
+    }
+
+    ///Encoding function writing to uninit slice.
+    ///
+    ///# Arguments
+    ///- `src` - Input to encode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///
+    ///Returns `Some` if successful, containing number of bytes written.
+    ///
+    ///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
+    #[inline]
+    pub fn encode_to_uninit(&self, src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Option<usize> {
+        let mut len = dst.len();
+        let dst = unsafe {
+            NonNull::new_unchecked(dst.as_mut_ptr() as *mut u8)
+        };
+        match unsafe { self.encode_to_raw(src, dst, &mut len) } {
+            true => Some(len),
+            false => None,
+        }
+    }
+
+    ///Decoding function writing to uninit slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to decode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///
+    ///Returns `Ok` if successful, containing number of bytes written.
+    ///
+    ///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
+    #[inline]
+    pub fn decode_to_uninit(&self, src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Result<usize, DecodeError> {
+        let mut len = dst.len();
+        let dst = unsafe {
+            NonNull::new_unchecked(dst.as_mut_ptr() as *mut u8)
+        };
+        unsafe {
+            self.decode_to_raw(src, dst, &mut len)?;
+        }
+        Ok(len)
+    }
+
+    ///Encoding function writing to slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to encode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///
+    ///Returns `Some` if successful, containing number of bytes written.
+    ///
+    ///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
+    #[inline(always)]
+    pub fn encode_to(&self, src: &[u8], dst: &mut [u8]) -> Option<usize> {
+        unsafe {
+            self.encode_to_uninit(src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
+        }
+    }
+
+    ///Decoding function writing to slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to decode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///Returns `Ok` if successful, containing number of bytes written.
+    ///
+    ///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
+    #[inline]
+    pub fn decode_to(&self, src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
+        unsafe {
+            self.decode_to_uninit(src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
+        }
+    }
+}
+
+#[inline(always)]
+///Encodes as many full chunks as possible using accelerated implementation, returning number of consumed bytes.
+///
+///SIMD is used when available, with SWAR for the rest.
+fn encode_bulk(table: &[u8; 16], src: &[u8], dst: *mut u8) -> usize {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    let consumed = super::simd::encode_hex(table, src, dst);
+    #[cfg(not(all(feature = "std", target_arch = "x86_64")))]
+    let consumed = 0;
+
+    let dst = unsafe {
+        dst.add(consumed * 2)
+    };
+    consumed + swar::encode_hex(table, &src[consumed..], dst)
+}
+
+#[inline(always)]
+///Decodes as many full chunks as possible using accelerated implementation, returning number of consumed characters.
+///
+///SIMD is used when available, with SWAR for the rest.
+///Stops at first chunk that contains anything but hex digits.
+fn decode_bulk(src: &[u8], dst: *mut u8) -> usize {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    let consumed = super::simd::decode_hex(src, dst);
+    #[cfg(not(all(feature = "std", target_arch = "x86_64")))]
+    let consumed = 0;
+
+    let dst = unsafe {
+        dst.add(consumed / 2)
+    };
+    consumed + swar::decode_hex(&REVERSE_TABLE, &src[consumed..], dst)
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impl {
+    extern crate alloc;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use super::{Codec, DecodeError};
+
+    impl Codec {
+        ///Encoding function returns vector with data written.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///- `src` - Input to encode;
+        ///
+        ///# Panics
+        ///
+        ///In case of required size to be too big
+        #[inline]
+        pub fn encode_into_vec(&self, src: &[u8]) -> Vec<u8> {
+            let required_len = self.encode_len(src.len());
+            //>= for case of zero sized input
+            assert!(required_len >= src.len());
+
+            let mut result = Vec::with_capacity(required_len);
+            unsafe {
+                let len = self.encode_inner(src, result.as_mut_ptr());
+                result.set_len(len);
+            }
+
+            result
+        }
+
+        ///Encoding function returns string.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///- `src` - Input to encode;
+        ///
+        ///# Panics
+        ///
+        ///In case of required size to be too big
+        #[inline]
+        pub fn encode_into_string(&self, src: &[u8]) -> String {
+            let result = self.encode_into_vec(src);
+
+            //Output contains only hex digits and separators
+            unsafe {
+                String::from_utf8_unchecked(result)
+            }
+        }
+
+        ///Decoding function returns vector with data written.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///
+        ///- `src` - Input to decode;
+        ///
+        ///# Result
+        ///
+        ///Returns `Ok` if successful, containing decoded output
+        ///
+        ///Returns `Err` if `src` is invalid input.
+        #[inline]
+        pub fn decode_into_vec(&self, src: &[u8]) -> Result<Vec<u8>, DecodeError> {
+            let mut result = Vec::with_capacity(self.decode_len(src));
+            unsafe {
+                let len = self.decode_inner(src, result.as_mut_ptr())?;
+                result.set_len(len);
+            }
+
+            Ok(result)
+        }
+    }
+}
@@ -0,0 +1,609 @@
+//! BASE32 (RFC 4648) and its variants
+//!
+//!Built-in alphabets:
+//!
+//!- [STANDARD_TABLE](STANDARD_TABLE) - RFC 4648 section 6;
+//!- [HEX_TABLE](HEX_TABLE) - RFC 4648 section 7, "Extended Hex", which preserves sort order;
+//!- [Z_TABLE](Z_TABLE) - z-base-32, designed to be read by humans. Used without padding;
+//!- [CROCKFORD_TABLE](CROCKFORD_TABLE) - Douglas Crockford's BASE32. Used without padding, decoding is case-insensitive, accepts `I`, `L` and `O` aliases and ignores hyphens.
+//!
+//!Every 5 bytes of input are encoded as 8 characters, with last group padded by `=` unless padding is [Forbidden](Padding::Forbidden).
+//!
+//!Encoding and decoding is done through [Codec](Codec) methods only, with prebuilt codec for every built-in alphabet,
+//!e.g. [STANDARD_CODEC](STANDARD_CODEC).
+
+use core::{mem, ptr};
+use core::ptr::NonNull;
+
+use super::{DecodeError, Padding, TableError, PAD, REVERSE_TABLE_SIZE, validate_table};
+
+///RFC 4648 alphabet
+pub static STANDARD_TABLE: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+///RFC 4648 "Extended Hex" alphabet
+pub static HEX_TABLE: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+///z-base-32 alphabet
+pub static Z_TABLE: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+///Crockford's alphabet
+pub static CROCKFORD_TABLE: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+///Codec which uses `STANDARD_TABLE`
+pub static STANDARD_CODEC: Codec<'static> = Codec::new(STANDARD_TABLE);
+///Codec which uses `HEX_TABLE`
+pub static HEX_CODEC: Codec<'static> = Codec::new(HEX_TABLE);
+///Codec which uses `Z_TABLE` without padding
+pub static Z_CODEC: Codec<'static> = Codec::new(Z_TABLE).with_padding(Padding::Forbidden);
+///Codec which uses `CROCKFORD_TABLE` without padding and with Crockford's decoding rules
+pub static CROCKFORD_CODEC: Codec<'static> = Codec::new(CROCKFORD_TABLE).with_padding(Padding::Forbidden).with_crockford(true);
+
+///Additional check symbols for values 32 to 36
+const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+const CHECK_MODULUS: u32 = 37;
+///Character ignored by Crockford's decoding
+const HYPHEN: u8 = b'-';
+
+///Validates custom character table, requiring it to contain only unique printable ASCII characters except padding.
+pub const fn validate_character_table(table: &[u8; 32]) -> Result<(), TableError> {
+    validate_table(table)
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to encode input of provided size (including padding).
+///
+///On overflow returns wrapped value.
+pub const fn encode_len(input: usize) -> usize {
+    input.wrapping_add(4).wrapping_div(5).wrapping_mul(8)
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to encode input of provided size (excluding padding).
+///
+///On overflow returns wrapped value.
+pub const fn encode_len_unpadded(input: usize) -> usize {
+    input.wrapping_mul(8).wrapping_add(4).wrapping_div(5)
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to decode provided input, excluding trailing padding.
+///
+///Any other character is counted as part of data, so for invalid input it is an upper bound.
+pub const fn decode_len(input: &[u8]) -> usize {
+    let mut len = input.len();
+    while len > 0 && input[len - 1] == PAD {
+        len -= 1;
+    }
+    len / 8 * 5 + len % 8 * 5 / 8
+}
+
+const fn build_reverse_table(table: &[u8; 32], case_insensitive: bool, crockford: bool) -> [i8; REVERSE_TABLE_SIZE] {
+    let mut reverse = [-1i8; REVERSE_TABLE_SIZE];
+
+    let mut idx = 0;
+    while idx < table.len() {
+        reverse[table[idx] as usize] = idx as i8;
+        idx += 1;
+    }
+
+    if case_insensitive || crockford {
+        idx = 0;
+        while idx < table.len() {
+            let other = match table[idx] {
+                ch @ b'a'..=b'z' => ch.to_ascii_uppercase(),
+                ch => ch.to_ascii_lowercase(),
+            };
+            //Table might contain both cases, in which case they are distinct
+            if reverse[other as usize] == -1 {
+                reverse[other as usize] = idx as i8;
+            }
+            idx += 1;
+        }
+    }
+
+    if crockford {
+        let aliases = [(b'I', b'1'), (b'i', b'1'), (b'L', b'1'), (b'l', b'1'), (b'O', b'0'), (b'o', b'0')];
+        idx = 0;
+        while idx < aliases.len() {
+            let (alias, target) = aliases[idx];
+            if reverse[alias as usize] == -1 {
+                reverse[alias as usize] = reverse[target as usize];
+            }
+            idx += 1;
+        }
+    }
+
+    reverse
+}
+
+#[inline(always)]
+///Returns remainder of data, as big-endian number, divided by 37.
+fn check_value(data: &[u8]) -> u32 {
+    data.iter().fold(0, |value, byte| (value * 256 + *byte as u32) % CHECK_MODULUS)
+}
+
+///BASE32 codec
+#[derive(Copy, Clone)]
+pub struct Codec<'a> {
+    table: &'a [u8; 32],
+    reverse: [i8; REVERSE_TABLE_SIZE],
+    strict: bool,
+    padding: Padding,
+    case_insensitive: bool,
+    crockford: bool,
+    check_symbol: bool,
+}
+
+impl<'a> Codec<'a> {
+    #[inline(always)]
+    ///Creates new codec, validating table with [validate_character_table](validate_character_table).
+    ///
+    ///# Panics
+    ///
+    ///If table is invalid. When used in const context it results in compile time error.
+    pub const fn new(table: &'a [u8; 32]) -> Self {
+        match Self::try_new(table) {
+            Ok(codec) => codec,
+            Err(error) => panic!("{}", error.as_str()),
+        }
+    }
+
+    #[inline]
+    ///Creates new codec, returning error if table is invalid.
+    pub const fn try_new(table: &'a [u8; 32]) -> Result<Self, TableError> {
+        match validate_character_table(table) {
+            Ok(()) => Ok(Self {
+                table,
+                reverse: build_reverse_table(table, false, false),
+                strict: false,
+                padding: Padding::Indifferent,
+                case_insensitive: false,
+                crockford: false,
+                check_symbol: false,
+            }),
+            Err(error) => Err(error),
+        }
+    }
+
+    #[inline(always)]
+    ///Sets strict decoding mode, in which unused bits of last character must be zero.
+    ///
+    ///As result every byte string has exactly one accepted encoding, save for case and skipped characters.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets padding policy, used by both encoding and decoding.
+    ///
+    ///Padding, if present, must complete last group of 8 characters.
+    ///
+    ///Default is [Indifferent](Padding::Indifferent).
+    pub const fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets whether decoding accepts letters of both cases.
+    ///
+    ///Encoding always uses characters of the table.
+    ///
+    ///Default is `false`.
+    pub const fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self.reverse = build_reverse_table(self.table, self.case_insensitive, self.crockford);
+        self
+    }
+
+    #[inline(always)]
+    ///Sets Crockford's decoding rules: letters of both cases are accepted, `I` and `L` decode as `1`, `O` decodes as `0` and hyphens are ignored.
+    ///
+    ///Default is `false`.
+    pub const fn with_crockford(mut self, crockford: bool) -> Self {
+        self.crockford = crockford;
+        self.reverse = build_reverse_table(self.table, self.case_insensitive, self.crockford);
+        self
+    }
+
+    #[inline(always)]
+    ///Sets whether Crockford's check symbol follows encoded data.
+    ///
+    ///Check symbol encodes remainder of data, as big-endian number, divided by 37,
+    ///using character table for values below 32 and `*~$=U` for the rest.
+    ///It is written after padding, if any, and decoding requires it to match data.
+    ///
+    ///Default is `false`.
+    pub const fn with_check_symbol(mut self, check_symbol: bool) -> Self {
+        self.check_symbol = check_symbol;
+        self
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes necessary to encode input of provided size, according to padding policy and check symbol.
+    ///
+    ///On overflow returns wrapped value.
+    pub const fn encode_len(&self, input: usize) -> usize {
+        let len = match self.padding {
+            Padding::Forbidden => encode_len_unpadded(input),
+            Padding::Required | Padding::Indifferent => encode_len(input),
+        };
+        len.wrapping_add(self.check_symbol as usize)
+    }
+
+    ///Returns exact number of bytes necessary to decode provided input.
+    ///
+    ///Skipped characters, padding and check symbol are not counted, any other character is counted as part of data.
+    pub fn decode_len(&self, input: &[u8]) -> usize {
+        let input = match self.check_symbol {
+            true => self.split_check_symbol(input).map_or(input, |(data, _)| data),
+            false => input,
+        };
+        let len = input.iter().filter(|ch| !self.is_skipped(**ch) && **ch != PAD).count();
+        len / 8 * 5 + len % 8 * 5 / 8
+    }
+
+    #[inline(always)]
+    const fn is_skipped(&self, ch: u8) -> bool {
+        self.crockford && ch == HYPHEN
+    }
+
+    #[inline(always)]
+    ///Splits input into data and position of check symbol, which is the last character that is not skipped.
+    fn split_check_symbol<'b>(&self, input: &'b [u8]) -> Option<(&'b [u8], usize)> {
+        let pos = input.iter().rposition(|ch| !self.is_skipped(*ch))?;
+        Some((&input[..pos], pos))
+    }
+
+    #[inline(always)]
+    fn check_symbol_value(&self, ch: u8) -> Option<u32> {
+        let value = self.reverse[ch as usize];
+        if value != -1 {
+            return Some(value as u32);
+        }
+
+        let ch = match self.case_insensitive || self.crockford {
+            true => ch.to_ascii_uppercase(),
+            false => ch,
+        };
+        CHECK_SYMBOLS.iter().position(|symbol| *symbol == ch).map(|pos| 32 + pos as u32)
+    }
+
+    ///Encodes input, that must fit `dst`, returning number of written bytes.
+    unsafe fn encode_inner(&self, src: &[u8], dst: *mut u8) -> usize {
+        let mut cursor = dst;
+
+        let mut chunks = src.chunks_exact(5);
+        for chunk in chunks.by_ref() {
+            let group = (chunk[0] as u64) << 32 | (chunk[1] as u64) << 24 | (chunk[2] as u64) << 16 | (chunk[3] as u64) << 8 | chunk[4] as u64;
+            for idx in 0..8 {
+                *cursor = self.table[(group >> (35 - 5 * idx) & 0x1f) as usize];
+                cursor = cursor.add(1);
+            }
+        }
+
+        let tail = chunks.remainder();
+        if !tail.is_empty() {
+            let mut chunk = [0u8; 5];
+            chunk[..tail.len()].copy_from_slice(tail);
+            let group = (chunk[0] as u64) << 32 | (chunk[1] as u64) << 24 | (chunk[2] as u64) << 16 | (chunk[3] as u64) << 8 | chunk[4] as u64;
+
+            let chars_len = encode_len_unpadded(tail.len());
+            for idx in 0..chars_len {
+                *cursor = self.table[(group >> (35 - 5 * idx) & 0x1f) as usize];
+                cursor = cursor.add(1);
+            }
+            if self.padding != Padding::Forbidden {
+                ptr::write_bytes(cursor, PAD, 8 - chars_len);
+                cursor = cursor.add(8 - chars_len);
+            }
+        }
+
+        if self.check_symbol {
+            let value = check_value(src);
+            *cursor = match value {
+                0..=31 => self.table[value as usize],
+                _ => CHECK_SYMBOLS[(value - 32) as usize],
+            };
+            cursor = cursor.add(1);
+        }
+
+        cursor as usize - dst as usize
+    }
+
+    ///Decodes input, that must fit `dst`, returning number of written bytes.
+    unsafe fn decode_inner(&self, src: &[u8], dst: *mut u8) -> Result<usize, DecodeError> {
+        let (src, check) = match self.check_symbol {
+            true => match self.split_check_symbol(src) {
+                Some((data, pos)) => match self.check_symbol_value(src[pos]) {
+                    Some(value) => (data, Some(value)),
+                    None => return Err(DecodeError::InvalidByte {
+                        offset: pos,
+                        byte: src[pos],
+                    }),
+                },
+                None => return Err(DecodeError::InvalidLength),
+            },
+            false => (src, None),
+        };
+
+        let mut group = 0u64;
+        let mut bits = 0;
+        let mut chars_len = 0;
+        let mut pad_len = 0;
+        let mut written = 0;
+
+        for (offset, ch) in src.iter().enumerate() {
+            let ch = *ch;
+            if self.is_skipped(ch) {
+                continue;
+            } else if ch == PAD {
+                pad_len += 1;
+                continue;
+            } else if pad_len > 0 {
+                return Err(DecodeError::InvalidPadding);
+            }
+
+            let value = self.reverse[ch as usize];
+            if value == -1 {
+                return Err(DecodeError::InvalidByte {
+                    offset,
+                    byte: ch,
+                });
+            }
+
+            group = group << 5 | value as u64;
+            bits += 5;
+            chars_len += 1;
+            if bits >= 8 {
+                bits -= 8;
+                *dst.add(written) = (group >> bits) as u8;
+                written += 1;
+            }
+        }
+
+        if let 1 | 3 | 6 = chars_len % 8 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let is_valid_padding = match self.padding {
+            Padding::Forbidden => pad_len == 0,
+            Padding::Required => (chars_len + pad_len) % 8 == 0 && pad_len < 8,
+            Padding::Indifferent => pad_len == 0 || ((chars_len + pad_len) % 8 == 0 && pad_len < 8),
+        };
+        if !is_valid_padding {
+            return Err(DecodeError::InvalidPadding);
+        } else if self.strict && group & ((1 << bits) - 1) != 0 {
+            return Err(DecodeError::TrailingBits);
+        }
+
+        if let Some(value) = check {
+            if check_value(core::slice::from_raw_parts(dst, written)) != value {
+                return Err(DecodeError::InvalidCheckSymbol);
+            }
+        }
+
+        Ok(written)
+    }
+
+    #[inline]
+    ///Raw encoding function.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to encode;
+    ///- `dst` - Output to write;
+    ///- `len` - Output length, modified with required size regardless of outcome, unless calculation wrapping happens.
+    ///
+    ///# Result
+    ///Returns `true` on success.
+    ///
+    ///Returns `false` if buffer overflow would to happen or required_len is too big.
+    ///
+    ///# Safety
+    ///
+    ///`dst` must be valid for writes of `len` bytes.
+    pub unsafe fn encode_to_raw(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
+        let required_len = self.encode_len(src.len());
+        if required_len < src.len() {
+            return false;
+        }
+
+        let is_fit = required_len <= *len;
+        if is_fit {
+            self.encode_inner(src, dst.as_ptr());
+        }
+        *len = required_len;
+        is_fit
+    }
+
+    #[inline]
+    ///Raw decoding function.
+    ///
+    ///# Arguments
+    ///- `src` - Input to decode;
+    ///- `dst` - Output to write;
+    ///- `len` - Output length, modified with number of bytes written on success or with required size if it is insufficient.
+    ///
+    ///# Result
+    ///Returns `Ok` on success.
+    ///
+    ///Returns `Err` if buffer overflow would to happen or `src` is invalid base32.
+    ///
+    ///# Safety
+    ///
+    ///`dst` must be valid for writes of `len` bytes.
+    pub unsafe fn decode_to_raw(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
+        let required_len = self.decode_len(src);
+        if required_len > *len {
+            *len = required_len;
+            return Err(DecodeError::OutputTooSmall {
+                required: required_len,
+            });
+        }
+
+        *len = self.decode_inner(src, dst.as_ptr())?;
+        Ok(())
+    }
+
+    ///Encoding function writing to uninit slice.
+    ///
+    ///# Arguments
+    ///- `src` - Input to encode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///
+    ///Returns `Some` if successful, containing number of bytes written.
+    ///
+    ///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
+    #[inline]
+    pub fn encode_to_uninit(&self, src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Option<usize> {
+        let mut len = dst.len();
+        let dst = unsafe {
+            NonNull::new_unchecked(dst.as_mut_ptr() as *mut u8)
+        };
+        match unsafe { self.encode_to_raw(src, dst, &mut len) } {
+            true => Some(len),
+            false => None,
+        }
+    }
+
+    ///Decoding function writing to uninit slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to decode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///
+    ///Returns `Ok` if successful, containing number of bytes written.
+    ///
+    ///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
+    #[inline]
+    pub fn decode_to_uninit(&self, src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Result<usize, DecodeError> {
+        let mut len = dst.len();
+        let dst = unsafe {
+            NonNull::new_unchecked(dst.as_mut_ptr() as *mut u8)
+        };
+        unsafe {
+            self.decode_to_raw(src, dst, &mut len)?;
+        }
+        Ok(len)
+    }
+
+    ///Encoding function writing to slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to encode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///
+    ///Returns `Some` if successful, containing number of bytes written.
+    ///
+    ///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
+    #[inline(always)]
+    pub fn encode_to(&self, src: &[u8], dst: &mut [u8]) -> Option<usize> {
+        unsafe {
+            self.encode_to_uninit(src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
+        }
+    }
+
+    ///Decoding function writing to slice.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to decode;
+    ///- `dst` - Output to write;
+    ///
+    ///# Result
+    ///Returns `Ok` if successful, containing number of bytes written.
+    ///
+    ///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
+    #[inline]
+    pub fn decode_to(&self, src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
+        unsafe {
+            self.decode_to_uninit(src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impl {
+    extern crate alloc;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use super::{Codec, DecodeError};
+
+    impl<'a> Codec<'a> {
+        ///Encoding function returns vector with data written.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///- `src` - Input to encode;
+        ///
+        ///# Panics
+        ///
+        ///In case of required size to be too big
+        #[inline]
+        pub fn encode_into_vec(&self, src: &[u8]) -> Vec<u8> {
+            let required_len = self.encode_len(src.len());
+            //>= for case of zero sized input
+            assert!(required_len >= src.len());
+
+            let mut result = Vec::with_capacity(required_len);
+            unsafe {
+                let len = self.encode_inner(src, result.as_mut_ptr());
+                result.set_len(len);
+            }
+
+            result
+        }
+
+        ///Encoding function returns string.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///- `src` - Input to encode;
+        ///
+        ///# Panics
+        ///
+        ///In case of required size to be too big
+        #[inline]
+        pub fn encode_into_string(&self, src: &[u8]) -> String {
+            let result = self.encode_into_vec(src);
+
+            //Table is validated to be ASCII
+            unsafe {
+                String::from_utf8_unchecked(result)
+            }
+        }
+
+        ///Decoding function returns vector with data written.
+        ///
+        ///Requires feature `alloc`.
+        ///
+        ///# Arguments
+        ///
+        ///- `src` - Input to decode;
+        ///
+        ///# Result
+        ///
+        ///Returns `Ok` if successful, containing decoded output
+        ///
+        ///Returns `Err` if `src` is invalid input.
+        #[inline]
+        pub fn decode_into_vec(&self, src: &[u8]) -> Result<Vec<u8>, DecodeError> {
+            let mut result = Vec::with_capacity(self.decode_len(src));
+            unsafe {
+                let len = self.decode_inner(src, result.as_mut_ptr())?;
+                result.set_len(len);
+            }
+
+            Ok(result)
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! Data URI (RFC 2397)
+//!
+//!Requires feature `alloc`.
+//!
+//!Writer always produces BASE64 form, while parser accepts both BASE64 and percent-encoded forms.
+
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{DecodeError, SKIP_WHITESPACE_CODEC, STANDARD_CODEC};
+
+const SCHEME: &str = "data:";
+const BASE64: &str = "base64";
+///Media type assumed when URI omits it.
+pub const DEFAULT_MEDIA_TYPE: &str = "text/plain";
+
+///Data URI parsing error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataUriError {
+    ///Input does not start with `data:`.
+    MissingScheme,
+    ///Header is not terminated by comma.
+    MissingComma,
+    ///Media type is not in form of `type/subtype`.
+    InvalidMediaType,
+    ///Parameter is not in form of `attribute=value`.
+    InvalidParameter {
+        ///Position of the parameter within input.
+        offset: usize,
+    },
+    ///Percent sign is not followed by two hexadecimal digits.
+    InvalidPercentEncoding {
+        ///Position of the percent sign within input.
+        offset: usize,
+    },
+    ///Data is not valid BASE64, with offset relative to the start of input.
+    Decode(DecodeError),
+}
+
+impl fmt::Display for DataUriError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataUriError::MissingScheme => fmt.write_str("Missing 'data:' scheme"),
+            DataUriError::MissingComma => fmt.write_str("Missing comma after header"),
+            DataUriError::InvalidMediaType => fmt.write_str("Invalid media type"),
+            DataUriError::InvalidParameter { offset } => fmt.write_fmt(format_args!("Invalid parameter at offset {}", offset)),
+            DataUriError::InvalidPercentEncoding { offset } => fmt.write_fmt(format_args!("Invalid percent encoding at offset {}", offset)),
+            DataUriError::Decode(error) => fmt.write_fmt(format_args!("Invalid data: {}", error)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DataUriError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DataUriError::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///Data URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataUri {
+    ///Media type, e.g. `image/png`.
+    pub media_type: String,
+    ///Media type parameters, e.g. `charset`, in order of appearance.
+    pub parameters: Vec<(String, String)>,
+    ///Decoded data.
+    pub data: Vec<u8>,
+}
+
+impl DataUri {
+    #[inline]
+    ///Creates new URI without parameters.
+    pub fn new<M: Into<String>, D: Into<Vec<u8>>>(media_type: M, data: D) -> Self {
+        Self {
+            media_type: media_type.into(),
+            parameters: Vec::new(),
+            data: data.into(),
+        }
+    }
+
+    #[inline]
+    ///Returns value of first parameter with `name`, compared case-insensitively.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    #[inline]
+    ///Writes URI in BASE64 form.
+    ///
+    ///Media type and parameters are written as they are, so they must not contain `;` or `,`.
+    pub fn encode(&self) -> String {
+        write(&self.media_type, &self.parameters, &self.data)
+    }
+}
+
+fn write(media_type: &str, parameters: &[(String, String)], data: &[u8]) -> String {
+    let payload = STANDARD_CODEC.encode_into_string(data);
+    let parameters_len = parameters.iter().fold(0, |len, (name, value)| len + name.len() + value.len() + 2);
+
+    let mut result = String::with_capacity(SCHEME.len() + media_type.len() + parameters_len + BASE64.len() + 2 + payload.len());
+    result.push_str(SCHEME);
+    result.push_str(media_type);
+    for (name, value) in parameters {
+        result.push(';');
+        result.push_str(name);
+        result.push('=');
+        result.push_str(value);
+    }
+    result.push(';');
+    result.push_str(BASE64);
+    result.push(',');
+    result.push_str(&payload);
+    result
+}
+
+#[inline(always)]
+///Checks RFC 2045 token character.
+fn is_token(ch: u8) -> bool {
+    ch.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?=".contains(&ch)
+}
+
+fn is_media_type(media_type: &[u8]) -> bool {
+    match media_type.iter().position(|ch| *ch == b'/') {
+        Some(pos) => {
+            let (kind, subtype) = (&media_type[..pos], &media_type[pos + 1..]);
+            !kind.is_empty() && !subtype.is_empty() && kind.iter().all(|ch| is_token(*ch)) && subtype.iter().all(|ch| is_token(*ch))
+        },
+        None => false,
+    }
+}
+
+#[inline(always)]
+fn hex_digit(ch: u8) -> Option<u8> {
+    match ch {
+        b'0'..=b'9' => Some(ch - b'0'),
+        b'a'..=b'f' => Some(ch - b'a' + 10),
+        b'A'..=b'F' => Some(ch - b'A' + 10),
+        _ => None,
+    }
+}
+
+///Decodes `%XX` sequences, copying other characters as they are.
+///
+///`base` is offset of `input`, used in errors.
+fn percent_decode(input: &[u8], base: usize) -> Result<Vec<u8>, DataUriError> {
+    let mut result = Vec::with_capacity(input.len());
+    let mut idx = 0;
+    while idx < input.len() {
+        let ch = input[idx];
+        if ch != b'%' {
+            result.push(ch);
+            idx += 1;
+            continue;
+        }
+
+        match (input.get(idx + 1).and_then(|ch| hex_digit(*ch)), input.get(idx + 2).and_then(|ch| hex_digit(*ch))) {
+            (Some(high), Some(low)) => result.push(high << 4 | low),
+            _ => return Err(DataUriError::InvalidPercentEncoding { offset: base + idx }),
+        }
+        idx += 3;
+    }
+    Ok(result)
+}
+
+///Maps offset within percent decoded `input` to offset within `input`.
+fn encoded_offset(input: &[u8], decoded: usize) -> usize {
+    let mut idx = 0;
+    for _ in 0..decoded {
+        idx += match input[idx] {
+            b'%' => 3,
+            _ => 1,
+        };
+    }
+    idx
+}
+
+///Parses data URI.
+///
+///Omitted media type defaults to [DEFAULT_MEDIA_TYPE](DEFAULT_MEDIA_TYPE) with `charset=US-ASCII`, unless parameters are present.
+///BASE64 data may be percent-encoded, contain whitespace and omit padding.
+pub fn parse(input: &str) -> Result<DataUri, DataUriError> {
+    let input = input.as_bytes();
+    match input.get(..SCHEME.len()) {
+        Some(scheme) if scheme.eq_ignore_ascii_case(SCHEME.as_bytes()) => (),
+        _ => return Err(DataUriError::MissingScheme),
+    }
+
+    let comma = match input.iter().position(|ch| *ch == b',') {
+        Some(comma) => comma,
+        None => return Err(DataUriError::MissingComma),
+    };
+    let header = &input[SCHEME.len()..comma];
+    let data_offset = comma + 1;
+    let payload = &input[data_offset..];
+
+    let mut segments = header.split(|ch| *ch == b';');
+    //Split always yields at least one segment
+    let media_type = segments.next().unwrap_or(b"");
+    if !media_type.is_empty() && !is_media_type(media_type) {
+        return Err(DataUriError::InvalidMediaType);
+    }
+
+    let mut is_base64 = false;
+    let mut parameters = Vec::new();
+    let mut offset = SCHEME.len() + media_type.len() + 1;
+    for segment in segments {
+        if is_base64 {
+            //base64 must be the last segment
+            return Err(DataUriError::InvalidParameter { offset });
+        } else if segment.eq_ignore_ascii_case(BASE64.as_bytes()) {
+            is_base64 = true;
+        } else {
+            let pos = match segment.iter().position(|ch| *ch == b'=') {
+                Some(pos) if pos > 0 && segment[..pos].iter().all(|ch| is_token(*ch)) => pos,
+                _ => return Err(DataUriError::InvalidParameter { offset }),
+            };
+            let name = String::from_utf8_lossy(&segment[..pos]).into_owned();
+            let value = String::from_utf8_lossy(&segment[pos + 1..]).into_owned();
+            parameters.push((name, value));
+        }
+        offset += segment.len() + 1;
+    }
+
+    let media_type = match media_type.is_empty() {
+        //Input is str, and media type consists of ASCII
+        false => String::from_utf8_lossy(media_type).into_owned(),
+        true => {
+            if parameters.is_empty() {
+                parameters.push(("charset".into(), "US-ASCII".into()));
+            }
+            DEFAULT_MEDIA_TYPE.into()
+        },
+    };
+
+    let data = match is_base64 {
+        false => percent_decode(payload, data_offset)?,
+        true => match payload.contains(&b'%') {
+            false => SKIP_WHITESPACE_CODEC.decode_into_vec(payload).map_err(|error| error.with_base_offset(data_offset)),
+            true => {
+                let decoded = percent_decode(payload, data_offset)?;
+                SKIP_WHITESPACE_CODEC.decode_into_vec(&decoded).map_err(|error| match error {
+                    DecodeError::InvalidByte { offset, byte } => DecodeError::InvalidByte {
+                        offset: data_offset + encoded_offset(payload, offset),
+                        byte,
+                    },
+                    error => error,
+                })
+            },
+        }.map_err(DataUriError::Decode)?,
+    };
+
+    Ok(DataUri {
+        media_type,
+        parameters,
+        data,
+    })
+}
+
+#[inline]
+///Writes URI in BASE64 form without parameters.
+///
+///Media type must not contain `;` or `,`.
+pub fn encode(media_type: &str, data: &[u8]) -> String {
+    write(media_type, &[], data)
+}
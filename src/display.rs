@@ -0,0 +1,94 @@
+use core::{fmt, str};
+
+use super::Codec;
+use super::stream::Encoder;
+
+///Size of stack buffer for encoded output.
+const BUFFER_SIZE: usize = 256;
+
+///Lazy formatter of BASE64, that encodes data directly into [Formatter](fmt::Formatter) without allocation.
+///
+///Width, fill and alignment flags are honoured, with left alignment by default.
+///`Debug` output is the same as `Display`.
+#[derive(Clone, Copy)]
+pub struct Base64Display<'a> {
+    codec: &'a Codec<'a>,
+    data: &'a [u8],
+}
+
+impl<'a> Base64Display<'a> {
+    #[inline]
+    ///Creates new formatter of `data`.
+    pub const fn new(codec: &'a Codec<'a>, data: &'a [u8]) -> Self {
+        Self {
+            codec,
+            data,
+        }
+    }
+}
+
+impl fmt::Display for Base64Display<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.codec.encode_len(self.data.len());
+        let fill_len = match fmt.width() {
+            Some(width) if width > len => width - len,
+            _ => return self.codec.encode_to_fmt(self.data, fmt),
+        };
+        let (before, after) = match fmt.align() {
+            Some(fmt::Alignment::Right) => (fill_len, 0),
+            Some(fmt::Alignment::Center) => (fill_len / 2, fill_len - fill_len / 2),
+            Some(fmt::Alignment::Left) | None => (0, fill_len),
+        };
+
+        let fill = fmt.fill();
+        for _ in 0..before {
+            fmt::Write::write_char(fmt, fill)?;
+        }
+        self.codec.encode_to_fmt(self.data, fmt)?;
+        for _ in 0..after {
+            fmt::Write::write_char(fmt, fill)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Base64Display<'_> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+impl<'a> Codec<'a> {
+    #[inline(always)]
+    ///Returns lazy formatter of `data`, see [Base64Display](Base64Display).
+    pub const fn display(&'a self, data: &'a [u8]) -> Base64Display<'a> {
+        Base64Display::new(self, data)
+    }
+
+    ///Encoding function writing to [fmt::Write](fmt::Write), e.g. `String` or [Formatter](fmt::Formatter).
+    ///
+    ///Output is encoded in chunks on stack, so no allocation happens.
+    ///
+    ///# Result
+    ///
+    ///Returns `Err` if `dst` fails to write, in which case it might contain part of output.
+    pub fn encode_to_fmt<W: fmt::Write + ?Sized>(&self, src: &[u8], dst: &mut W) -> fmt::Result {
+        let mut encoder = Encoder::new(self);
+        let mut buffer = [0u8; BUFFER_SIZE];
+
+        let mut input = src;
+        while !input.is_empty() {
+            let (consumed, written) = encoder.push(input, &mut buffer);
+            input = &input[consumed..];
+            //Character table is validated to be ASCII
+            dst.write_str(unsafe { str::from_utf8_unchecked(&buffer[..written]) })?;
+        }
+
+        match encoder.finish(&mut buffer) {
+            Some(written) => dst.write_str(unsafe { str::from_utf8_unchecked(&buffer[..written]) }),
+            //Buffer always fits the last group
+            None => Err(fmt::Error),
+        }
+    }
+}
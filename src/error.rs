@@ -0,0 +1,123 @@
+use core::fmt;
+
+///Decoding error
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    ///Input contains byte that is not part of character table.
+    InvalidByte {
+        ///Position of the byte within input.
+        offset: usize,
+        ///Byte value.
+        byte: u8,
+    },
+    ///Input length cannot be result of encoding (e.g. single dangling character).
+    InvalidLength,
+    ///Padding is missing, excessive or in wrong place.
+    InvalidPadding,
+    ///Last character contains non-zero bits that do not belong to any decoded byte.
+    TrailingBits,
+    ///Check symbol does not match decoded data.
+    InvalidCheckSymbol,
+    ///Output buffer cannot fit decoded data.
+    OutputTooSmall {
+        ///Number of bytes required to decode input.
+        required: usize,
+    },
+}
+
+impl DecodeError {
+    #[inline]
+    ///Shifts offset of invalid byte by `base`, for errors found in part of input.
+    pub(crate) fn with_base_offset(self, base: usize) -> Self {
+        match self {
+            DecodeError::InvalidByte { offset, byte } => DecodeError::InvalidByte {
+                offset: base + offset,
+                byte,
+            },
+            error => error,
+        }
+    }
+
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            DecodeError::InvalidByte { .. } => "Invalid byte",
+            DecodeError::InvalidLength => "Invalid input length",
+            DecodeError::InvalidPadding => "Invalid padding",
+            DecodeError::TrailingBits => "Non-zero trailing bits in last character",
+            DecodeError::InvalidCheckSymbol => "Check symbol does not match data",
+            DecodeError::OutputTooSmall { .. } => "Output buffer is too small",
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidByte { offset, byte } => fmt.write_fmt(format_args!("{} 0x{:02x} at offset {}", self.as_str(), byte, offset)),
+            DecodeError::InvalidLength | DecodeError::InvalidPadding | DecodeError::TrailingBits | DecodeError::InvalidCheckSymbol => fmt.write_str(self.as_str()),
+            DecodeError::OutputTooSmall { required } => fmt.write_fmt(format_args!("{}, requires {} bytes", self.as_str(), required)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {
+}
+
+///Character table validation error
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TableError {
+    ///Table contains non-ASCII character.
+    NonAscii {
+        ///Position of the character within table.
+        index: usize,
+        ///Character value.
+        byte: u8,
+    },
+    ///Table contains control character or space.
+    NonPrintable {
+        ///Position of the character within table.
+        index: usize,
+        ///Character value.
+        byte: u8,
+    },
+    ///Table contains padding character.
+    PadCollision {
+        ///Position of the character within table.
+        index: usize,
+    },
+    ///Table contains the same character more than once.
+    DuplicateSymbol {
+        ///Position of the first occurrence.
+        first: usize,
+        ///Position of the second occurrence.
+        second: usize,
+        ///Character value.
+        byte: u8,
+    },
+}
+
+impl TableError {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            TableError::NonAscii { .. } => "Character table contains non-ASCII character",
+            TableError::NonPrintable { .. } => "Character table contains non-printable character",
+            TableError::PadCollision { .. } => "Character table contains padding character",
+            TableError::DuplicateSymbol { .. } => "Character table contains duplicate character",
+        }
+    }
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::NonAscii { index, byte } | TableError::NonPrintable { index, byte } => fmt.write_fmt(format_args!("{}: 0x{:02x} at index {}", self.as_str(), byte, index)),
+            TableError::PadCollision { index } => fmt.write_fmt(format_args!("{} at index {}", self.as_str(), index)),
+            TableError::DuplicateSymbol { first, second, byte } => fmt.write_fmt(format_args!("{}: 0x{:02x} at indices {} and {}", self.as_str(), byte, first, second)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TableError {
+}
@@ -0,0 +1,217 @@
+//! Streaming adapters over `std::io`
+
+use std::io;
+
+use super::{Codec, DecodeError, encode_len};
+use super::stream::{Decoder, Encoder};
+
+///Number of input bytes encoded at once, multiple of 3 so that only last chunk can be padded.
+const ENCODE_CHUNK_SIZE: usize = 3 * 256;
+///Number of input characters buffered for decoding, multiple of 4.
+const DECODE_CHUNK_SIZE: usize = 4 * 256;
+
+#[inline]
+fn write_all<W: io::Write>(writer: &mut W, buffer: &[u8], pos: &mut usize) -> io::Result<()> {
+    while *pos < buffer.len() {
+        match writer.write(&buffer[*pos..]) {
+            Ok(0) => return Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write encoded data")),
+            Ok(written) => *pos += written,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn invalid_data(error: DecodeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+///Encoder that writes BASE64 of everything written into it to underlying writer.
+///
+///Requires feature `std`.
+///
+///Input is buffered until it forms complete 3 byte group, hence [finish](EncoderWriter::finish)
+///must be called at the end to write last group and padding.
+///Dropping encoder without calling it loses buffered input.
+pub struct EncoderWriter<'a, W: io::Write> {
+    encoder: Encoder<'a>,
+    writer: W,
+    //Encoded output, not yet written
+    output: [u8; encode_len(ENCODE_CHUNK_SIZE)],
+    output_pos: usize,
+    output_len: usize,
+}
+
+impl<'a, W: io::Write> EncoderWriter<'a, W> {
+    #[inline]
+    ///Creates new encoder, writing to `writer`.
+    pub fn new(codec: &'a Codec<'a>, writer: W) -> Self {
+        Self {
+            encoder: Encoder::new(codec),
+            writer,
+            output: [0; encode_len(ENCODE_CHUNK_SIZE)],
+            output_pos: 0,
+            output_len: 0,
+        }
+    }
+
+    #[inline(always)]
+    ///Access underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    #[inline(always)]
+    ///Access underlying writer mutably.
+    ///
+    ///Writing into it directly corrupts encoded output.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    fn write_output(&mut self) -> io::Result<()> {
+        write_all(&mut self.writer, &self.output[..self.output_len], &mut self.output_pos)?;
+        self.output_pos = 0;
+        self.output_len = 0;
+        Ok(())
+    }
+
+    ///Writes remaining input with padding, flushes and returns underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_output()?;
+        //Output buffer is empty, so it always fits last group
+        self.output_len = match self.encoder.finish(&mut self.output) {
+            Some(size) => size,
+            None => return Err(io::Error::new(io::ErrorKind::WriteZero, "failed to fit last encoded group")),
+        };
+        self.write_output()?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<'a, W: io::Write> io::Write for EncoderWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_output()?;
+
+        let (consumed, written) = self.encoder.push(buf, &mut self.output);
+        self.output_len = written;
+        Ok(consumed)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_output()?;
+        self.writer.flush()
+    }
+}
+
+///Decoder that reads BASE64 from underlying reader, returning decoded data.
+///
+///Requires feature `std`.
+///
+///Decoding follows rules of provided [Codec](Codec).
+///Invalid input results in error of kind `InvalidData`, containing [DecodeError](crate::DecodeError)
+///with offset relative to the start of stream.
+pub struct DecoderReader<'a, R: io::Read> {
+    decoder: Decoder<'a>,
+    reader: R,
+    //Input characters, not yet decoded
+    input: [u8; DECODE_CHUNK_SIZE],
+    input_start: usize,
+    input_end: usize,
+    eof: bool,
+    finished: bool,
+    //Decoded data, not yet returned
+    output: [u8; DECODE_CHUNK_SIZE / 4 * 3],
+    output_pos: usize,
+    output_len: usize,
+}
+
+impl<'a, R: io::Read> DecoderReader<'a, R> {
+    #[inline]
+    ///Creates new decoder, reading from `reader`.
+    pub fn new(codec: &'a Codec<'a>, reader: R) -> Self {
+        Self {
+            decoder: Decoder::new(codec),
+            reader,
+            input: [0; DECODE_CHUNK_SIZE],
+            input_start: 0,
+            input_end: 0,
+            eof: false,
+            finished: false,
+            output: [0; DECODE_CHUNK_SIZE / 4 * 3],
+            output_pos: 0,
+            output_len: 0,
+        }
+    }
+
+    #[inline(always)]
+    ///Access underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    #[inline(always)]
+    ///Access underlying reader mutably.
+    ///
+    ///Reading from it directly corrupts decoded output.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    #[inline(always)]
+    ///Returns underlying reader, discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn fill_input(&mut self) -> io::Result<()> {
+        loop {
+            match self.reader.read(&mut self.input) {
+                Ok(size) => {
+                    self.input_start = 0;
+                    self.input_end = size;
+                    self.eof = size == 0;
+                    break Ok(())
+                },
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => break Err(error),
+            }
+        }
+    }
+}
+
+impl<'a, R: io::Read> io::Read for DecoderReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if self.output_pos < self.output_len {
+                let size = core::cmp::min(buf.len(), self.output_len - self.output_pos);
+                buf[..size].copy_from_slice(&self.output[self.output_pos..self.output_pos + size]);
+                self.output_pos += size;
+                return Ok(size);
+            } else if self.finished {
+                return Ok(0);
+            }
+
+            self.output_pos = 0;
+            self.output_len = 0;
+            if self.input_start < self.input_end {
+                let (consumed, written) = self.decoder.push(&self.input[self.input_start..self.input_end], &mut self.output).map_err(invalid_data)?;
+                self.input_start += consumed;
+                self.output_len = written;
+            } else if self.eof {
+                self.output_len = self.decoder.finish(&mut self.output).map_err(invalid_data)?;
+                self.finished = true;
+            } else {
+                self.fill_input()?;
+            }
+        }
+    }
+}
@@ -3,6 +3,8 @@
 //!## Features
 //!
 //!- `alloc` - Enables usage of heap based collections;
+//!- `std` - Enables `std::error::Error` implementation for errors and SIMD acceleration with runtime CPU detection on `x86_64`. Implies `alloc`.
+//!- `serde` - Enables [serde](serde) modules for `#[serde(with = "...")]`. Implies `alloc`.
 //!
 //!## API
 //!
@@ -10,18 +12,55 @@
 //!- [uninit](uninit) - Contains functions to work with unintialized slices.
 //!- [vec](vec) - Contains high level functions that returns `Vec`. Requires `alloc` feature.
 //!- [string](string) - Contains high level functions that returns `String`. Requires `alloc` feature.
+//!- [stream](stream) - Contains incremental encoder and decoder, for data arriving in fragments.
+//!- [pem](pem) - Contains PEM documents parser and writer. Requires `alloc` feature.
+//!- [armor](armor) - Contains OpenPGP ASCII armor parser and writer. Requires `alloc` feature.
+//!- [data_uri](data_uri) - Contains data URI parser and writer. Requires `alloc` feature.
+//!- [serde](serde) - Contains modules to serialize bytes as BASE64 string. Requires `serde` feature.
+//!- [io](io) - Contains streaming adapters over `std::io`. Requires `std` feature.
+//!- [base32](base32) - Contains BASE32 codec with RFC 4648, hex, z-base-32 and Crockford alphabets.
+//!- [base16](base16) - Contains BASE16 (hex) codec with optional separators.
+//!- [ascii85](ascii85) - Contains Ascii85 codec with Adobe and btoa variants.
+//!- [z85](z85) - Contains Z85 codec of ZeroMQ, with helpers for CURVE keys.
+//!- [Base64Display](Base64Display) - Lazy formatter, that encodes directly into `core::fmt::Formatter`.
 //!- [Codec](Codec) - Wrapper that allows to pre-built lookup table for decoding. Useful if you want to safe tiny bit on building lookup table.
 
 #![no_std]
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::style))]
+#![allow(clippy::style)]
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+pub use error::{DecodeError, TableError};
+mod display;
+pub use display::Base64Display;
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+mod simd;
+mod swar;
 pub mod raw;
 pub mod uninit;
+pub mod stream;
+pub mod base32;
+pub mod base16;
+pub mod ascii85;
+pub mod z85;
+mod array;
 #[cfg(feature = "alloc")]
 pub mod vec;
 #[cfg(feature = "alloc")]
 pub mod string;
+#[cfg(feature = "alloc")]
+pub mod pem;
+#[cfg(feature = "alloc")]
+pub mod armor;
+#[cfg(feature = "alloc")]
+pub mod data_uri;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "serde")]
+pub mod serde;
 
 use core::mem;
 
@@ -30,15 +69,88 @@ pub const PAD: u8 = b'=';
 ///Default character table used by based64
 pub static STANDARD_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
 ///Alternative table URL safe.
-pub static URL_TABLE: &[u8; 64] = b"ABCDEFGHIJLKMNOPQRSTUVWXYZabcdefghijlkmnopqrstuvwxyz0123456789-_";
+pub static URL_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
 ///Codec which uses `STANDARD_TABLE`
 pub static STANDARD_CODEC: Codec<'static> = Codec::new(STANDARD_TABLE);
 ///Codec which uses `URL_TABLE`
 pub static URL_CODEC: Codec<'static> = Codec::new(URL_TABLE);
+#[cfg(feature = "alloc")]
+///Codec which uses `STANDARD_TABLE` and skips any whitespace, used to decode line wrapped text formats.
+pub(crate) static SKIP_WHITESPACE_CODEC: Codec<'static> = Codec::new(STANDARD_TABLE).with_skip_whitespace(true);
+
+///Checks that table is RFC 4648 alphabet with specified last two characters.
+const fn is_rfc4648_table(table: &[u8; 64], char62: u8, char63: u8) -> bool {
+    let mut idx = 0;
+    while idx < table.len() {
+        let expected = match idx as u8 {
+            idx @ 0..=25 => b'A' + idx,
+            idx @ 26..=51 => b'a' + idx - 26,
+            idx @ 52..=61 => b'0' + idx - 52,
+            62 => char62,
+            _ => char63,
+        };
+
+        if table[idx] != expected {
+            return false;
+        }
+
+        idx += 1;
+    }
+
+    true
+}
+
+//Built-in tables must be exactly RFC 4648 alphabets (section 4 and 5 respectively)
+const _: () = assert!(is_rfc4648_table(STANDARD_TABLE, b'+', b'/'));
+const _: () = assert!(is_rfc4648_table(URL_TABLE, b'-', b'_'));
+
+///Validates custom character table, requiring it to contain only unique printable ASCII characters except padding.
+pub const fn validate_character_table(table: &[u8; 64]) -> Result<(), TableError> {
+    validate_table(table)
+}
+
+///Validates character table of any size.
+const fn validate_table(table: &[u8]) -> Result<(), TableError> {
+    //Position + 1 of already seen character
+    let mut seen = [0u8; 128];
+    let mut idx = 0;
+    while idx < table.len() {
+        let byte = table[idx];
+        if !byte.is_ascii() {
+            return Err(TableError::NonAscii {
+                index: idx,
+                byte,
+            });
+        } else if !byte.is_ascii_graphic() {
+            return Err(TableError::NonPrintable {
+                index: idx,
+                byte,
+            });
+        } else if byte == PAD {
+            return Err(TableError::PadCollision {
+                index: idx,
+            });
+        } else if seen[byte as usize] != 0 {
+            return Err(TableError::DuplicateSymbol {
+                first: seen[byte as usize] as usize - 1,
+                second: idx,
+                byte,
+            });
+        }
+
+        seen[byte as usize] = idx as u8 + 1;
+        idx += 1;
+    }
+
+    Ok(())
+}
 
 #[inline]
 ///Validates custom character table by requiring user to provide table of specific size
 ///containing only ASCII characters.
+///
+///This is minimal requirement for encoded output to be valid UTF-8, use
+///[validate_character_table](validate_character_table) to verify that table can be used for decoding.
 pub const fn assert_valid_character_table(table: &[u8; 64]) -> bool {
     let mut idx = 0;
     while idx < table.len() {
@@ -79,32 +191,45 @@ pub const fn encode_len(input: usize) -> usize {
     input.wrapping_mul(4).wrapping_div(3).wrapping_add(3) & !3
 }
 
+#[inline(always)]
+///Returns number of bytes necessary to encode input of provided size without padding.
+///
+///On overflow returns wrapped value.
+pub const fn encode_len_unpadded(input: usize) -> usize {
+    input.wrapping_mul(4).wrapping_add(2).wrapping_div(3)
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to encode input of provided size (including padding) with line wrapping.
+///
+///On overflow returns wrapped value.
+pub const fn encode_len_wrapped(input: usize, line_wrap: LineWrap) -> usize {
+    line_wrap.wrapped_len(encode_len(input))
+}
+
 ///Returns number of bytes necessary to decode provided input.
 ///
-///In case of not rounded input, assumes the worst.
+///Exact for well formed input, otherwise it is upper bound of what decoder can write.
 pub const fn decode_len(input: &[u8]) -> usize {
     let len = input.len();
-    if len == 0 {
-        return 0
+    let mut pad_num = 0;
+    while pad_num < 3 && pad_num < len && input[len - pad_num - 1] == PAD {
+        pad_num += 1;
     }
+    decode_len_of(len, pad_num)
+}
 
+///Returns number of bytes necessary to decode `len` characters, ending with `pad_num` padding characters.
+const fn decode_len_of(len: usize, pad_num: usize) -> usize {
     let unused_num = len & 3;
     if unused_num != 0 {
         //unpadded (probably)
-        len.wrapping_div(4).wrapping_mul(3) + unused_num
+        //each remaining character carries 6 bits, so only full bytes count
+        len.wrapping_div(4).wrapping_mul(3) + unused_num * 3 / 4
     } else {
         //padded so it is simply
         //len / 4 * 3
-        let result = len.wrapping_div(4).wrapping_mul(3);
-        if input[len - 1] != PAD {
-            result
-        } else if input[len - 2] != PAD {
-            result - 1
-        } else if input[len - 3] != PAD {
-            result - 2
-        } else {
-            result - 3
-        }
+        len.wrapping_div(4).wrapping_mul(3) - pad_num
     }
 }
 
@@ -123,7 +248,7 @@ pub const fn decode_len(input: &[u8]) -> usize {
 #[inline]
 pub fn encode(table: &[u8; 64], src: &[u8], dst: &mut [u8]) -> Option<usize> {
     unsafe {
-        uninit::encode(table, src, mem::transmute(dst))
+        uninit::encode(table, src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
     }
 }
 
@@ -135,13 +260,136 @@ pub fn encode(table: &[u8; 64], src: &[u8], dst: &mut [u8]) -> Option<usize> {
 ///- `dst` - Output to write;
 ///
 ///# Result
-///Returns `Some` if successful, containing number of bytes written.
+///Returns `Ok` if successful, containing number of bytes written.
 ///
-///Returns `None` if data cannot be encoded due to insufficient buffer size or invalid input.
+///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
 #[inline]
-pub fn decode(table: &[u8; 64], src: &[u8], dst: &mut [u8]) -> Option<usize> {
+pub fn decode(table: &[u8; 64], src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
     unsafe {
-        uninit::decode(table, src, mem::transmute(dst))
+        uninit::decode(table, src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
+    }
+}
+
+///Padding policy of [Codec](Codec)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Padding {
+    ///Padding is written on encoding and must be present when decoding.
+    Required,
+    ///Padding is never written on encoding and must be absent when decoding.
+    Forbidden,
+    ///Padding is written on encoding, while decoding accepts input with or without it.
+    Indifferent,
+}
+
+///Line ending, inserted by [LineWrap](LineWrap)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineEnding {
+    ///`\n`
+    Lf,
+    ///`\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    #[inline(always)]
+    ///Returns characters of line ending.
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+        }
+    }
+}
+
+///Line wrapping of encoded output.
+///
+///Line ending is inserted after every `width` characters, except at the end of output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LineWrap {
+    width: usize,
+    ending: LineEnding,
+}
+
+impl LineWrap {
+    ///MIME (RFC 2045) wrapping: 76 characters per line with `CRLF`
+    pub const MIME: Self = Self::new(76, LineEnding::CrLf);
+    ///PEM (RFC 7468) wrapping: 64 characters per line with `LF`
+    pub const PEM: Self = Self::new(64, LineEnding::Lf);
+
+    #[inline(always)]
+    ///Creates new wrapping with `width` characters per line, excluding line ending.
+    ///
+    ///# Panics
+    ///
+    ///If `width` is zero.
+    pub const fn new(width: usize, ending: LineEnding) -> Self {
+        assert!(width > 0, "Line width must not be zero");
+        Self {
+            width,
+            ending,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of characters per line, excluding line ending.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline(always)]
+    ///Returns line ending.
+    pub const fn ending(&self) -> LineEnding {
+        self.ending
+    }
+
+    #[inline(always)]
+    ///Returns length of `len` characters after wrapping.
+    ///
+    ///On overflow returns wrapped value.
+    pub const fn wrapped_len(&self, len: usize) -> usize {
+        self.wrapped_len_from(0, len)
+    }
+
+    #[inline(always)]
+    ///Returns number of line endings inserted into `len` characters, written after `column` characters of line.
+    pub(crate) const fn line_breaks(&self, column: usize, len: usize) -> usize {
+        match len {
+            0 => 0,
+            len => column.wrapping_add(len - 1) / self.width,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn wrapped_len_from(&self, column: usize, len: usize) -> usize {
+        len.wrapping_add(self.line_breaks(column, len).wrapping_mul(self.ending.as_bytes().len()))
+    }
+
+    #[inline(always)]
+    ///Returns column after writing `len` characters from `column`.
+    pub(crate) const fn next_column(&self, column: usize, len: usize) -> usize {
+        match len {
+            0 => column,
+            len => (column + len - 1) % self.width + 1,
+        }
+    }
+
+    ///Returns number of characters, that can be written from `column` into `size` bytes.
+    pub(crate) const fn fit_len(&self, column: usize, mut size: usize) -> usize {
+        let first = self.width - column;
+        if size <= first {
+            return size;
+        }
+
+        size -= first;
+        let ending_len = self.ending.as_bytes().len();
+        let line_len = self.width + ending_len;
+        let lines = size / line_len;
+        size -= lines * line_len;
+        let mut result = first + lines * self.width;
+        if size > ending_len {
+            result += size - ending_len;
+        }
+        result
     }
 }
 
@@ -149,20 +397,146 @@ pub fn decode(table: &[u8; 64], src: &[u8], dst: &mut [u8]) -> Option<usize> {
 #[derive(Copy, Clone)]
 pub struct Codec<'a> {
     table: &'a [u8; 64],
-    reverse: [i8; REVERSE_TABLE_SIZE]
+    reverse: [i8; REVERSE_TABLE_SIZE],
+    strict: bool,
+    padding: Padding,
+    line_wrap: Option<LineWrap>,
+    skip_whitespace: bool,
 }
 
 impl<'a> Codec<'a> {
     #[inline(always)]
-    ///Creates new codec, validating that table contains only ASCII characters.
+    ///Creates new codec, validating table with [validate_character_table](validate_character_table).
+    ///
+    ///# Panics
+    ///
+    ///If table is invalid. When used in const context it results in compile time error.
     pub const fn new(table: &'a [u8; 64]) -> Self {
-        assert!(assert_valid_character_table(table));
-        Self {
-            table,
-            reverse: build_reverse_table(table),
+        match Self::try_new(table) {
+            Ok(codec) => codec,
+            Err(error) => panic!("{}", error.as_str()),
         }
     }
 
+    #[inline]
+    ///Creates new codec, returning error if table is invalid.
+    pub const fn try_new(table: &'a [u8; 64]) -> Result<Self, TableError> {
+        match validate_character_table(table) {
+            Ok(()) => Ok(Self {
+                table,
+                reverse: build_reverse_table(table),
+                strict: false,
+                padding: Padding::Indifferent,
+                line_wrap: None,
+                skip_whitespace: false,
+            }),
+            Err(error) => Err(error),
+        }
+    }
+
+    #[inline(always)]
+    ///Sets strict decoding mode.
+    ///
+    ///In strict mode decoder accepts only canonical RFC 4648 encoding:
+    ///
+    ///- Input must be padded, unless padding is [Forbidden](Padding::Forbidden) in which case it must be absent;
+    ///- Padding can only be at the end of input and its length must match number of missing characters;
+    ///- Unused bits of last character must be zero.
+    ///
+    ///As result every byte string has exactly one accepted encoding.
+    ///
+    ///By default decoder is lenient, stopping at first padding character and ignoring trailing bits.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets padding policy, used by both encoding and decoding.
+    ///
+    ///Default is [Indifferent](Padding::Indifferent).
+    pub const fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets line wrapping of encoded output.
+    ///
+    ///When set, decoding skips line breaks (`\r` and `\n`) anywhere in input.
+    ///
+    ///Default is `None`.
+    pub const fn with_line_wrap(mut self, line_wrap: Option<LineWrap>) -> Self {
+        self.line_wrap = line_wrap;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets whether decoding skips ASCII whitespace (space, `\t`, `\n`, `\x0C` and `\r`) anywhere in input.
+    ///
+    ///Offsets in errors still refer to the original input.
+    ///
+    ///Default is `false`.
+    pub const fn with_skip_whitespace(mut self, skip_whitespace: bool) -> Self {
+        self.skip_whitespace = skip_whitespace;
+        self
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes necessary to encode input of provided size, according to padding policy and line wrapping.
+    ///
+    ///On overflow returns wrapped value.
+    pub const fn encode_len(&self, input: usize) -> usize {
+        let len = self.unwrapped_encode_len(input);
+        match self.line_wrap {
+            Some(line_wrap) => line_wrap.wrapped_len(len),
+            None => len,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn unwrapped_encode_len(&self, input: usize) -> usize {
+        match self.padding {
+            Padding::Forbidden => encode_len_unpadded(input),
+            Padding::Required | Padding::Indifferent => encode_len(input),
+        }
+    }
+
+    ///Returns number of bytes necessary to decode provided input, excluding characters skipped by codec.
+    ///
+    ///Exact for well formed input, otherwise it is upper bound of what decoder can write.
+    pub fn decode_len(&self, input: &[u8]) -> usize {
+        if !self.has_skipped() {
+            return decode_len(input);
+        }
+
+        let skipped = input.iter().map(|ch| self.is_skipped(*ch) as usize).sum::<usize>();
+        let mut pad_num = 0;
+        for ch in input.iter().rev().filter(|ch| !self.is_skipped(**ch)) {
+            if *ch != PAD || pad_num == 3 {
+                break;
+            }
+            pad_num += 1;
+        }
+
+        decode_len_of(input.len() - skipped, pad_num)
+    }
+
+    #[inline(always)]
+    ///Returns whether decoding ignores any characters.
+    pub(crate) const fn has_skipped(&self) -> bool {
+        self.skip_whitespace || self.line_wrap.is_some()
+    }
+
+    #[inline(always)]
+    ///Returns whether character is ignored when decoding.
+    pub(crate) const fn is_skipped(&self, ch: u8) -> bool {
+        //Without branches, so that it can be vectorized
+        let line_break = (ch == b'\r') | (ch == b'\n');
+        let whitespace = (ch == b' ') | (ch == b'\t') | (ch == b'\x0C');
+        (line_break & self.has_skipped()) | (whitespace & self.skip_whitespace)
+    }
+
     #[inline(always)]
     ///Access prebuilt instance of codec with `STANDARD_TABLE`
     pub fn standard() -> &'static Codec<'static> {
@@ -191,7 +565,9 @@ impl<'a> Codec<'a> {
     ///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
     #[inline(always)]
     pub fn encode_to(&self, src: &[u8], dst: &mut [u8]) -> Option<usize> {
-        encode(self.table, src, dst)
+        unsafe {
+            self.encode_to_uninit(src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
+        }
     }
 
     ///Decoding function writing to slice.
@@ -202,13 +578,13 @@ impl<'a> Codec<'a> {
     ///- `dst` - Output to write;
     ///
     ///# Result
-    ///Returns `Some` if successful, containing number of bytes written.
+    ///Returns `Ok` if successful, containing number of bytes written.
     ///
-    ///Returns `None` if data cannot be encoded due to insufficient buffer size or invalid input.
+    ///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
     #[inline]
-    pub fn decode_to(&self, src: &[u8], dst: &mut [u8]) -> Option<usize> {
+    pub fn decode_to(&self, src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
         unsafe {
-            self.decode_to_uninit(src, mem::transmute(dst))
+            self.decode_to_uninit(src, mem::transmute::<&mut [u8], &mut [mem::MaybeUninit<u8>]>(dst))
         }
     }
 }
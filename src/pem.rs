@@ -0,0 +1,296 @@
+//! PEM (RFC 7468) documents
+//!
+//!Requires feature `alloc`.
+//!
+//!Parser accepts explanatory text around blocks, `CRLF` line endings and RFC 1421 headers.
+//!Writer produces strict RFC 7468 format with 64 characters per line.
+
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{DecodeError, LineWrap, SKIP_WHITESPACE_CODEC, STANDARD_CODEC};
+
+pub(crate) const BEGIN: &str = "-----BEGIN ";
+pub(crate) const END: &str = "-----END ";
+pub(crate) const BOUNDARY_END: &str = "-----";
+
+///PEM parsing error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PemError {
+    ///Input contains no `-----BEGIN` line.
+    MissingBegin,
+    ///Line starting with `-----BEGIN` or `-----END` is malformed.
+    InvalidBoundary {
+        ///Position of the line within input.
+        offset: usize,
+    },
+    ///Block is not terminated by `-----END` line.
+    MissingEnd {
+        ///Label of the block.
+        label: String,
+    },
+    ///Label of `-----END` line differs from `-----BEGIN`.
+    LabelMismatch {
+        ///Label of `-----BEGIN` line.
+        begin: String,
+        ///Label of `-----END` line.
+        end: String,
+    },
+    ///Header line is not in form of `Name: value`, or headers are not followed by empty line.
+    InvalidHeader {
+        ///Position of the line within input.
+        offset: usize,
+    },
+    ///Body is not valid BASE64, with offset relative to the start of input.
+    Decode(DecodeError),
+}
+
+impl fmt::Display for PemError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PemError::MissingBegin => fmt.write_str("Missing -----BEGIN line"),
+            PemError::InvalidBoundary { offset } => fmt.write_fmt(format_args!("Invalid encapsulation boundary at offset {}", offset)),
+            PemError::MissingEnd { label } => fmt.write_fmt(format_args!("Missing -----END line for '{}'", label)),
+            PemError::LabelMismatch { begin, end } => fmt.write_fmt(format_args!("Label mismatch: BEGIN '{}', END '{}'", begin, end)),
+            PemError::InvalidHeader { offset } => fmt.write_fmt(format_args!("Invalid header at offset {}", offset)),
+            PemError::Decode(error) => fmt.write_fmt(format_args!("Invalid body: {}", error)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PemError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PemError::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///PEM block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pem {
+    ///Label, e.g. `CERTIFICATE`.
+    pub label: String,
+    ///RFC 1421 headers, e.g. `Proc-Type`, in order of appearance.
+    pub headers: Vec<(String, String)>,
+    ///Decoded body.
+    pub contents: Vec<u8>,
+}
+
+impl Pem {
+    #[inline]
+    ///Creates new block without headers.
+    pub fn new<L: Into<String>, C: Into<Vec<u8>>>(label: L, contents: C) -> Self {
+        Self {
+            label: label.into(),
+            headers: Vec::new(),
+            contents: contents.into(),
+        }
+    }
+
+    #[inline]
+    ///Writes block, wrapping body at 64 characters per line with `LF`.
+    ///
+    ///Label and headers are written as they are, so they must not contain line breaks.
+    pub fn encode(&self) -> String {
+        write(&self.label, &self.headers, &self.contents)
+    }
+}
+
+fn write(label: &str, headers: &[(String, String)], contents: &[u8]) -> String {
+    let codec = STANDARD_CODEC.with_line_wrap(Some(LineWrap::PEM));
+    let body = codec.encode_into_string(contents);
+
+    let mut result = String::with_capacity(body.len() + 2 * (label.len() + BEGIN.len() + BOUNDARY_END.len() + 1) + 1);
+    result.push_str(BEGIN);
+    result.push_str(label);
+    result.push_str(BOUNDARY_END);
+    result.push('\n');
+
+    if !headers.is_empty() {
+        for (name, value) in headers {
+            result.push_str(name);
+            result.push_str(": ");
+            result.push_str(value);
+            result.push('\n');
+        }
+        result.push('\n');
+    }
+
+    if !body.is_empty() {
+        result.push_str(&body);
+        result.push('\n');
+    }
+
+    result.push_str(END);
+    result.push_str(label);
+    result.push_str(BOUNDARY_END);
+    result.push('\n');
+    result
+}
+
+///Lines of input with their offsets, excluding line ending and trailing whitespace.
+#[derive(Clone)]
+pub(crate) struct Lines<'a> {
+    pub(crate) input: &'a [u8],
+    ///Offset of next line
+    pub(crate) offset: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = (usize, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.input.len() {
+            return None;
+        }
+
+        let start = self.offset;
+        let rest = &self.input[start..];
+        let line = match rest.iter().position(|ch| *ch == b'\n') {
+            Some(pos) => {
+                self.offset += pos + 1;
+                &rest[..pos]
+            },
+            None => {
+                self.offset = self.input.len();
+                rest
+            },
+        };
+
+        let len = line.iter().rposition(|ch| !ch.is_ascii_whitespace()).map_or(0, |pos| pos + 1);
+        Some((start, &line[..len]))
+    }
+}
+
+///Extracts label of boundary line, which starts with `prefix`.
+pub(crate) fn boundary_label(line: &[u8], prefix: &str) -> Option<String> {
+    let label = line[prefix.len()..].strip_suffix(BOUNDARY_END.as_bytes())?;
+
+    //RFC 7468: printable characters, that cannot start or end with space or hyphen
+    let is_valid = label.iter().all(|ch| ch.is_ascii_graphic() || *ch == b' ')
+                   && !label.starts_with(b"-") && !label.ends_with(b"-")
+                   && !label.starts_with(b" ") && !label.ends_with(b" ");
+    match is_valid {
+        //Printable ASCII is always valid UTF-8
+        true => Some(String::from_utf8_lossy(label).into_owned()),
+        false => None,
+    }
+}
+
+///Parses RFC 1421 headers from lines after `-----BEGIN`, returning them and offset of body.
+fn parse_headers(lines: &mut Lines<'_>, headers: &mut Vec<(String, String)>) -> Result<usize, PemError> {
+    let body_offset = lines.offset;
+
+    //BASE64 never contains colon, so it indicates presence of headers
+    match lines.clone().next() {
+        Some((_, line)) if line.contains(&b':') => (),
+        _ => return Ok(body_offset),
+    }
+
+    while let Some((offset, line)) = lines.next() {
+        if line.is_empty() {
+            return Ok(lines.offset);
+        } else if line[0] == b' ' || line[0] == b'\t' {
+            //Continuation of previous header
+            match headers.last_mut() {
+                Some((_, value)) => value.push_str(&String::from_utf8_lossy(line)),
+                None => return Err(PemError::InvalidHeader { offset }),
+            }
+        } else if line.starts_with(END.as_bytes()) {
+            return Err(PemError::InvalidHeader { offset });
+        } else {
+            let pos = match line.iter().position(|ch| *ch == b':') {
+                Some(pos) => pos,
+                None => return Err(PemError::InvalidHeader { offset }),
+            };
+            let name = String::from_utf8_lossy(&line[..pos]).trim().into();
+            let value = String::from_utf8_lossy(&line[pos + 1..]).trim().into();
+            headers.push((name, value));
+        }
+    }
+
+    Err(PemError::InvalidHeader { offset: lines.input.len() })
+}
+
+///Parses block starting after `-----BEGIN` line, returning it with offset past `-----END` line.
+fn parse_block(lines: &mut Lines<'_>, label: String) -> Result<(Pem, usize), PemError> {
+    let mut headers = Vec::new();
+    let body_offset = parse_headers(lines, &mut headers)?;
+
+    for (offset, line) in lines.by_ref() {
+        if !line.starts_with(END.as_bytes()) {
+            continue;
+        }
+
+        let end = boundary_label(line, END).ok_or(PemError::InvalidBoundary { offset })?;
+        if end != label {
+            return Err(PemError::LabelMismatch {
+                begin: label,
+                end,
+            });
+        }
+
+        let contents = SKIP_WHITESPACE_CODEC.decode_into_vec(&lines.input[body_offset..offset]).map_err(|error| PemError::Decode(error.with_base_offset(body_offset)))?;
+        let pem = Pem {
+            label,
+            headers,
+            contents,
+        };
+        return Ok((pem, lines.offset));
+    }
+
+    Err(PemError::MissingEnd {
+        label,
+    })
+}
+
+///Parses next block, skipping any text before it.
+fn parse_next(input: &[u8], offset: usize) -> Result<Option<(Pem, usize)>, PemError> {
+    let mut lines = Lines {
+        input,
+        offset,
+    };
+
+    while let Some((offset, line)) = lines.next() {
+        if line.starts_with(BEGIN.as_bytes()) {
+            let label = boundary_label(line, BEGIN).ok_or(PemError::InvalidBoundary { offset })?;
+            return parse_block(&mut lines, label).map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+///Parses first PEM block in `input`, ignoring any text around it.
+pub fn parse(input: &[u8]) -> Result<Pem, PemError> {
+    match parse_next(input, 0)? {
+        Some((pem, _)) => Ok(pem),
+        None => Err(PemError::MissingBegin),
+    }
+}
+
+///Parses all PEM blocks in `input`, ignoring any text between them.
+///
+///Returns empty vector if there are no blocks.
+pub fn parse_many(input: &[u8]) -> Result<Vec<Pem>, PemError> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+    while let Some((pem, next)) = parse_next(input, offset)? {
+        result.push(pem);
+        offset = next;
+    }
+    Ok(result)
+}
+
+///Writes single PEM block without headers.
+///
+///Label must not contain line breaks.
+pub fn encode(label: &str, contents: &[u8]) -> String {
+    write(label, &[], contents)
+}
@@ -1,7 +1,8 @@
 //! Low level functions
 
-use core::ptr::NonNull;
-use super::{PAD, encode_len, decode_len, REVERSE_TABLE_SIZE, build_reverse_table, Codec};
+use core::ptr::{self, NonNull};
+use super::{swar, PAD, Padding, LineWrap, encode_len, decode_len, REVERSE_TABLE_SIZE, build_reverse_table, Codec, DecodeError};
+use super::stream::Decoder;
 
 #[cold]
 #[inline(never)]
@@ -9,12 +10,63 @@ fn unlikely_false() -> bool {
     false
 }
 
-pub(crate) fn encode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut usize) {
-    let mut it = src.as_ptr();
+#[cold]
+#[inline(never)]
+fn invalid_byte<T>(offset: usize, byte: u8) -> Result<T, DecodeError> {
+    Err(DecodeError::InvalidByte {
+        offset,
+        byte,
+    })
+}
+
+#[inline(always)]
+///Encodes as many full chunks as possible using accelerated implementation, returning number of consumed bytes.
+///
+///SIMD is used when available, with SWAR for the rest.
+fn encode_bulk(table: &[u8; 64], src: &[u8], dst: *mut u8) -> usize {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    let consumed = super::simd::encode(table, src, dst);
+    #[cfg(not(all(feature = "std", target_arch = "x86_64")))]
+    let consumed = 0;
+
+    let dst = unsafe {
+        dst.add(consumed / 3 * 4)
+    };
+    consumed + swar::encode(table, &src[consumed..], dst)
+}
+
+#[inline(always)]
+///Decodes as many full chunks as possible using accelerated implementation, returning number of consumed characters.
+///
+///SIMD is used when available, with SWAR for the rest.
+///Stops at first chunk that contains anything but characters from the table.
+pub(crate) fn decode_bulk(table: &[u8; 64], reverse_table: &[i8; REVERSE_TABLE_SIZE], src: &[u8], dst: *mut u8, capacity: usize) -> usize {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    let consumed = super::simd::decode(table, src, dst, capacity);
+    #[cfg(not(all(feature = "std", target_arch = "x86_64")))]
+    let consumed = {
+        let _ = table;
+        0
+    };
+
+    let written = consumed / 4 * 3;
+    let dst = unsafe {
+        dst.add(written)
+    };
+    consumed + swar::decode(reverse_table, &src[consumed..], dst, capacity - written)
+}
+
+pub(crate) fn encode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut usize, pad: bool) {
+    let consumed = encode_bulk(table, src, dst.as_ptr());
+    let mut it = unsafe {
+        src.as_ptr().add(consumed)
+    };
     let it_end = unsafe {
-        it.add(src.len())
+        src.as_ptr().add(src.len())
+    };
+    let mut cursor = unsafe {
+        dst.as_ptr().add(consumed / 3 * 4)
     };
-    let mut cursor = dst.as_ptr();
     while (it_end as usize) - (it as usize) >= 3 {
         unsafe {
             *cursor = *table.get_unchecked(
@@ -55,8 +107,10 @@ pub(crate) fn encode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len:
                 );
                 cursor = cursor.add(1);
 
-                *cursor = PAD;
-                cursor = cursor.add(1);
+                if pad {
+                    *cursor = PAD;
+                    cursor = cursor.add(1);
+                }
             } else {
                 *cursor = *table.get_unchecked(
                     (((*it) & 0x03).wrapping_shl(4) | (*it.add(1)).wrapping_shr(4)) as usize
@@ -69,14 +123,51 @@ pub(crate) fn encode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len:
                 cursor = cursor.add(1);
             }
 
-            *cursor = PAD;
-            cursor = cursor.add(1);
+            if pad {
+                *cursor = PAD;
+                cursor = cursor.add(1);
+            }
         }
     }
 
     *len = cursor as usize - dst.as_ptr() as usize;
 }
 
+///Inserts line endings into `len` characters at `dst`, written after `column` characters of line.
+///
+///Characters are moved in place, returning new length.
+///
+///# Safety
+///
+///`dst` must be valid for writes of resulting length.
+pub(crate) unsafe fn wrap_lines(line_wrap: &LineWrap, dst: *mut u8, len: usize, column: usize) -> usize {
+    let line_breaks = line_wrap.line_breaks(column, len);
+    if line_breaks == 0 {
+        return len;
+    }
+
+    let ending = line_wrap.ending().as_bytes();
+    let width = line_wrap.width();
+    //Column never exceeds width, so first line can be empty
+    let first_len = width - column;
+
+    let mut src_end = len;
+    let mut dst_end = len + line_breaks * ending.len();
+    let result = dst_end;
+    //Move lines from the end, so that nothing is overwritten before it is moved
+    for line in (0..line_breaks).rev() {
+        let src_start = first_len + line * width;
+        let line_len = src_end - src_start;
+        dst_end -= line_len;
+        ptr::copy(dst.add(src_start), dst.add(dst_end), line_len);
+        dst_end -= ending.len();
+        ptr::copy_nonoverlapping(ending.as_ptr(), dst.add(dst_end), ending.len());
+        src_end = src_start;
+    }
+
+    result
+}
+
 #[inline]
 ///Raw encoding function.
 ///
@@ -90,6 +181,10 @@ pub(crate) fn encode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len:
 ///Returns `true` on success.
 ///
 ///Returns `false` if buffer overflow would to happen or required_len is too big.
+///
+///# Safety
+///
+///`dst` must be valid for writes of `len` bytes.
 pub unsafe fn encode(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
     let required_len = encode_len(src.len());
     if required_len < src.len() {
@@ -100,20 +195,138 @@ pub unsafe fn encode(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut u
         return false;
     }
 
-    encode_inner(table, src, dst, len);
+    encode_inner(table, src, dst, len, true);
+    true
+}
+
+#[inline(always)]
+///Checks that rest of input is correctly padded chunk, when padding is required.
+///
+///`chunk` is the rest of input, starting with chunk that has padding at `idx`.
+pub(crate) const fn is_padded_chunk(chunk: &[u8], idx: usize) -> bool {
+    //Only 1 or 2 characters can be padded and nothing can follow padding
+    if idx < 2 || chunk.len() != 4 {
+        return false;
+    }
+
+    let mut pad_idx = idx;
+    while pad_idx < chunk.len() {
+        if chunk[pad_idx] != PAD {
+            return false;
+        }
+        pad_idx += 1;
+    }
     true
 }
 
-pub(crate) fn decode_inner_with_rev(reverse_table: &[i8; REVERSE_TABLE_SIZE], mut src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
+#[inline(always)]
+///Decodes 4 characters into 3 bytes.
+pub(crate) const fn decode_quad(quad: [u8; 4]) -> [u8; 3] {
+    [
+        quad[0] << 2 | quad[1] >> 4,
+        quad[1] << 4 | quad[2] >> 2,
+        quad[2] << 6 | quad[3],
+    ]
+}
+
+#[inline(always)]
+///Decodes character, which cannot be padding, with `offset` used for error.
+pub(crate) const fn decode_char(reverse_table: &[i8; REVERSE_TABLE_SIZE], ch: u8, offset: usize) -> Result<u8, DecodeError> {
+    match reverse_table[ch as usize] {
+        -1 if ch == PAD => Err(DecodeError::InvalidPadding),
+        -1 => Err(DecodeError::InvalidByte {
+            offset,
+            byte: ch,
+        }),
+        pos => Ok(pos as u8),
+    }
+}
+
+#[inline(always)]
+///Returns length of last chunk in strict mode, which is validated by [strict_tail_data_len](strict_tail_data_len).
+pub(crate) const fn strict_tail_len(len: usize) -> usize {
+    match len & 3 {
+        0 if len == 0 => 0,
+        0 => 4,
+        unused_num => unused_num,
+    }
+}
+
+///Validates padding of last chunk in strict mode, returning number of characters before padding.
+pub(crate) const fn strict_tail_data_len(tail: &[u8], padding: Padding) -> Result<usize, DecodeError> {
+    let mut data_len = 0;
+    while data_len < tail.len() && tail[data_len] != PAD {
+        data_len += 1;
+    }
+
+    if data_len == 1 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    match padding {
+        Padding::Forbidden => if data_len != tail.len() {
+            return Err(DecodeError::InvalidPadding);
+        },
+        //Either there is no data, padding is missing or data follows padding
+        Padding::Required | Padding::Indifferent => {
+            if data_len == 0 || tail.len() != 4 {
+                return Err(DecodeError::InvalidPadding);
+            }
+            let mut pad_idx = data_len;
+            while pad_idx < tail.len() {
+                if tail[pad_idx] != PAD {
+                    return Err(DecodeError::InvalidPadding);
+                }
+                pad_idx += 1;
+            }
+        },
+    }
+
+    Ok(data_len)
+}
+
+#[inline(always)]
+///Checks that unused bits of last character in chunk of `data_len` characters are zero.
+pub(crate) const fn check_trailing_bits(quad: &[u8; 4], data_len: usize) -> Result<(), DecodeError> {
+    let is_trailing = match data_len {
+        3 => quad[2] & 0x3 != 0,
+        2 => quad[1] & 0xf != 0,
+        _ => false,
+    };
+    match is_trailing {
+        true => Err(DecodeError::TrailingBits),
+        false => Ok(()),
+    }
+}
+
+pub(crate) fn decode_inner_with_rev(table: &[u8; 64], reverse_table: &[i8; REVERSE_TABLE_SIZE], mut src: &[u8], dst: NonNull<u8>, len: &mut usize, padding: Padding) -> Result<(), DecodeError> {
     let mut cursor = dst.as_ptr();
     let mut chunk = [0u8; 4];
     let mut chunk_len = 0;
 
+    let mut offset = decode_bulk(table, reverse_table, src, cursor, *len);
+    src = &src[offset..];
+    cursor = unsafe {
+        cursor.add(offset / 4 * 3)
+    };
+
     macro_rules! get_base64_byte {
         ($src:ident[$idx:literal]) => {{
             let ch = match $src.get($idx) {
                 Some(ch) if *ch != PAD => *ch,
-                _ => {
+                Some(_) => {
+                    match padding {
+                        Padding::Forbidden => return Err(DecodeError::InvalidPadding),
+                        Padding::Required if !is_padded_chunk($src, $idx) => return Err(DecodeError::InvalidPadding),
+                        _ => (),
+                    }
+                    chunk_len = $idx - 1;
+                    break;
+                },
+                None => {
+                    if $idx != 0 && padding == Padding::Required {
+                        return Err(DecodeError::InvalidPadding);
+                    }
                     chunk_len = $idx - 1;
                     break;
                 },
@@ -123,7 +336,7 @@ pub(crate) fn decode_inner_with_rev(reverse_table: &[i8; REVERSE_TABLE_SIZE], mu
                 reverse_table.get_unchecked(ch as usize)
             };
             if *pos == -1 {
-                return unlikely_false()
+                return invalid_byte(offset + $idx, ch)
             }
             *pos as u8
         }}
@@ -145,6 +358,7 @@ pub(crate) fn decode_inner_with_rev(reverse_table: &[i8; REVERSE_TABLE_SIZE], mu
         }
 
         src = &src[4..];
+        offset += 4;
 
         if src.is_empty() {
             break;
@@ -174,13 +388,85 @@ pub(crate) fn decode_inner_with_rev(reverse_table: &[i8; REVERSE_TABLE_SIZE], mu
     }
 
     *len = cursor as usize - dst.as_ptr() as usize;
-    true
+    Ok(())
+}
+
+#[inline(always)]
+fn decode_symbol(reverse_table: &[i8; REVERSE_TABLE_SIZE], src: &[u8], idx: usize, offset: usize) -> Result<u8, DecodeError> {
+    decode_char(reverse_table, src[idx], offset + idx)
+}
+
+///Strict decoding, accepting only canonical encoding.
+///
+///Input must be padded (unless padding is forbidden), padding can only be at the end and unused bits of last character must be zero.
+pub(crate) fn decode_inner_strict(table: &[u8; 64], reverse_table: &[i8; REVERSE_TABLE_SIZE], src: &[u8], dst: NonNull<u8>, len: &mut usize, padding: Padding) -> Result<(), DecodeError> {
+    let mut cursor = dst.as_ptr();
+
+    let tail_len = strict_tail_len(src.len());
+    let (body, tail) = src.split_at(src.len() - tail_len);
+
+    let mut offset = decode_bulk(table, reverse_table, body, cursor, *len);
+    cursor = unsafe {
+        cursor.add(offset / 4 * 3)
+    };
+    for chunk in body[offset..].chunks_exact(4) {
+        let chunk = [
+            decode_symbol(reverse_table, chunk, 0, offset)?,
+            decode_symbol(reverse_table, chunk, 1, offset)?,
+            decode_symbol(reverse_table, chunk, 2, offset)?,
+            decode_symbol(reverse_table, chunk, 3, offset)?,
+        ];
+
+        unsafe {
+            *cursor = chunk[0].wrapping_shl(2).wrapping_add((chunk[1] & 0x30).wrapping_shr(4));
+            cursor = cursor.add(1);
+            *cursor = (chunk[1] & 0xf).wrapping_shl(4).wrapping_add((chunk[2] & 0x3c).wrapping_shr(2));
+            cursor = cursor.add(1);
+            *cursor = (chunk[2] & 0x3).wrapping_shl(6).wrapping_add(chunk[3]);
+            cursor = cursor.add(1);
+        }
+
+        offset += 4;
+    }
+
+    if !tail.is_empty() {
+        let data_len = strict_tail_data_len(tail, padding)?;
 
+        let mut chunk = [0u8; 4];
+        for idx in 0..data_len {
+            chunk[idx] = decode_symbol(reverse_table, tail, idx, offset)?;
+        }
+        check_trailing_bits(&chunk, data_len)?;
+
+        match data_len {
+            4 => unsafe {
+                *cursor = chunk[0].wrapping_shl(2).wrapping_add((chunk[1] & 0x30).wrapping_shr(4));
+                cursor = cursor.add(1);
+                *cursor = (chunk[1] & 0xf).wrapping_shl(4).wrapping_add((chunk[2] & 0x3c).wrapping_shr(2));
+                cursor = cursor.add(1);
+                *cursor = (chunk[2] & 0x3).wrapping_shl(6).wrapping_add(chunk[3]);
+                cursor = cursor.add(1);
+            },
+            3 => unsafe {
+                *cursor = chunk[0].wrapping_shl(2).wrapping_add((chunk[1] & 0x30).wrapping_shr(4));
+                cursor = cursor.add(1);
+                *cursor = (chunk[1] & 0xf).wrapping_shl(4).wrapping_add((chunk[2] & 0x3c).wrapping_shr(2));
+                cursor = cursor.add(1);
+            },
+            _ => unsafe {
+                *cursor = chunk[0].wrapping_shl(2).wrapping_add((chunk[1] & 0x30).wrapping_shr(4));
+                cursor = cursor.add(1);
+            },
+        }
+    }
+
+    *len = cursor as usize - dst.as_ptr() as usize;
+    Ok(())
 }
 
 #[inline(always)]
-pub(crate) fn decode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
-    decode_inner_with_rev(&build_reverse_table(table), src, dst, len)
+pub(crate) fn decode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
+    decode_inner_with_rev(table, &build_reverse_table(table), src, dst, len, Padding::Indifferent)
 }
 
 #[inline]
@@ -189,18 +475,24 @@ pub(crate) fn decode_inner(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len:
 ///# Arguments
 ///- `src` - Input to decode;
 ///- `dst` - Output to write;
-///- `len` - Output length, modified with required size regardless of outcome.
+///- `len` - Output length, modified with number of bytes written on success or with required size if it is insufficient.
 ///
 ///# Result
-///Returns `true` on success.
+///Returns `Ok` on success.
+///
+///Returns `Err` if buffer overflow would to happen or `src` is invalid base64.
+///
+///# Safety
 ///
-///Returns `false` if buffer overflow would to happen or `src` is empty or invalid base64.
-pub unsafe fn decode(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
+///`dst` must be valid for writes of `len` bytes.
+pub unsafe fn decode(table: &[u8; 64], src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
     let required_len = decode_len(src);
 
-    if required_len == 0 {
-        *len = 0;
-        return true;
+    if required_len > *len {
+        *len = required_len;
+        return Err(DecodeError::OutputTooSmall {
+            required: required_len,
+        });
     }
 
     decode_inner(table, src, dst, len)
@@ -220,8 +512,36 @@ impl<'a> Codec<'a> {
     ///Returns `true` on success.
     ///
     ///Returns `false` if buffer overflow would to happen or required_len is too big.
+    ///
+    ///# Safety
+    ///
+    ///`dst` must be valid for writes of `len` bytes.
     pub unsafe fn encode_to_raw(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
-        encode(self.table, src, dst, len)
+        let required_len = self.encode_len(src.len());
+        if required_len < src.len() {
+            return unlikely_false();
+        } else if required_len > *len {
+            *len = required_len;
+            return false;
+        }
+
+        self.encode_inner(src, dst, len);
+        true
+    }
+
+    #[inline(always)]
+    pub(crate) fn encode_inner(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) {
+        self.encode_unwrapped(src, dst, len);
+        if let Some(line_wrap) = &self.line_wrap {
+            *len = unsafe {
+                wrap_lines(line_wrap, dst.as_ptr(), *len, 0)
+            };
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn encode_unwrapped(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) {
+        encode_inner(self.table, src, dst, len, self.padding != Padding::Forbidden)
     }
 
     #[inline]
@@ -230,20 +550,72 @@ impl<'a> Codec<'a> {
     ///# Arguments
     ///- `src` - Input to decode;
     ///- `dst` - Output to write;
-    ///- `len` - Output length, modified with required size regardless of outcome.
+    ///- `len` - Output length, modified with number of bytes written on success or with required size if it is insufficient.
     ///
     ///# Result
-    ///Returns `true` on success.
+    ///Returns `Ok` on success.
+    ///
+    ///Returns `Err` if buffer overflow would to happen or `src` is invalid base64.
+    ///
+    ///# Safety
     ///
-    ///Returns `false` if buffer overflow would to happen or `src` is empty or invalid base64.
-    pub unsafe fn decode_to_raw(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> bool {
-        let required_len = decode_len(src);
+    ///`dst` must be valid for writes of `len` bytes.
+    pub unsafe fn decode_to_raw(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
+        let mut required_len = decode_len(src);
 
-        if required_len == 0 {
-            *len = 0;
-            return true;
+        //Skipped characters are counted as input, so exact length might still fit
+        if required_len > *len && self.has_skipped() {
+            required_len = self.decode_len(src);
         }
 
-        decode_inner_with_rev(&self.reverse, src, dst, len)
+        if required_len > *len {
+            *len = required_len;
+            return Err(DecodeError::OutputTooSmall {
+                required: required_len,
+            });
+        }
+
+        self.decode_inner(src, dst, len)
+    }
+
+    #[inline(always)]
+    pub(crate) fn decode_inner(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
+        if !self.has_skipped() {
+            return self.decode_plain(src, dst, len);
+        }
+
+        //Usually there is nothing to skip, so input is decoded as it is first, if output fits it.
+        //Skipped characters are invalid otherwise, hence any error means input needs to be decoded again.
+        let capacity = *len;
+        if capacity >= decode_len(src) && self.decode_plain(src, dst, len).is_ok() {
+            return Ok(());
+        }
+
+        *len = capacity;
+        self.decode_skipping(src, dst, len)
+    }
+
+    #[cold]
+    ///Decodes input in parts between skipped characters.
+    fn decode_skipping(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
+        let mut decoder = Decoder::new(self);
+        unsafe {
+            let (consumed, written) = decoder.push_raw(src, dst.as_ptr(), *len)?;
+            if consumed < src.len() {
+                return Err(DecodeError::OutputTooSmall {
+                    required: self.decode_len(src),
+                });
+            }
+            *len = written + decoder.finish_raw(dst.as_ptr().add(written), *len - written)?;
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub(crate) fn decode_plain(&self, src: &[u8], dst: NonNull<u8>, len: &mut usize) -> Result<(), DecodeError> {
+        match self.strict {
+            true => decode_inner_strict(self.table, &self.reverse, src, dst, len, self.padding),
+            false => decode_inner_with_rev(self.table, &self.reverse, src, dst, len, self.padding),
+        }
     }
 }
@@ -0,0 +1,178 @@
+//! Serde integration, serializing bytes as BASE64 string
+//!
+//!Requires feature `serde`.
+//!
+//!Modules are intended for `#[serde(with = "...")]` attribute:
+//!
+//!- [standard](standard) - Uses [STANDARD_CODEC](crate::STANDARD_CODEC);
+//!- [url_safe](url_safe) - Uses [URL_CODEC](crate::URL_CODEC).
+//!
+//!Serialization accepts any `AsRef<[u8]>`, e.g. `Vec<u8>`, `[u8; N]` or `bytes::Bytes`, and writes string without intermediate allocation.
+//!Deserialization accepts any `TryFrom<Vec<u8>>`, e.g. `Vec<u8>`, `[u8; N]` or `bytes::Bytes`, decoding borrowed string directly.
+//!
+//!Each module contains `option` sub-module for `Option` of the same types.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::{de, Deserializer, Serialize, Serializer};
+
+use super::Codec;
+
+fn serialize<T: AsRef<[u8]> + ?Sized, S: Serializer>(codec: &Codec<'_>, value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&codec.display(value.as_ref()))
+}
+
+struct Base64Visitor<T> {
+    codec: &'static Codec<'static>,
+    _result: PhantomData<T>,
+}
+
+impl<'de, T: TryFrom<Vec<u8>>> de::Visitor<'de> for Base64Visitor<T> {
+    type Value = T;
+
+    #[inline]
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("BASE64 string")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        self.visit_bytes(value.as_bytes())
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        let data = self.codec.decode_into_vec(value).map_err(|error| E::custom(format_args!("invalid BASE64: {}", error)))?;
+        let len = data.len();
+        T::try_from(data).map_err(|_| E::invalid_length(len, &"BASE64 string of different length"))
+    }
+}
+
+fn deserialize<'de, T: TryFrom<Vec<u8>>, D: Deserializer<'de>>(codec: &'static Codec<'static>, deserializer: D) -> Result<T, D::Error> {
+    deserializer.deserialize_str(Base64Visitor {
+        codec,
+        _result: PhantomData,
+    })
+}
+
+///Value serialized with particular codec.
+struct Encoded<'a, T: ?Sized> {
+    codec: &'static Codec<'static>,
+    value: &'a T,
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Serialize for Encoded<'_, T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.codec, self.value, serializer)
+    }
+}
+
+fn serialize_option<T: AsRef<[u8]>, S: Serializer>(codec: &'static Codec<'static>, value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.serialize_some(&Encoded {
+            codec,
+            value,
+        }),
+        None => serializer.serialize_none(),
+    }
+}
+
+struct OptionVisitor<T> {
+    codec: &'static Codec<'static>,
+    _result: PhantomData<T>,
+}
+
+impl<'de, T: TryFrom<Vec<u8>>> de::Visitor<'de> for OptionVisitor<T> {
+    type Value = Option<T>;
+
+    #[inline]
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("optional BASE64 string")
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserialize(self.codec, deserializer).map(Some)
+    }
+}
+
+fn deserialize_option<'de, T: TryFrom<Vec<u8>>, D: Deserializer<'de>>(codec: &'static Codec<'static>, deserializer: D) -> Result<Option<T>, D::Error> {
+    deserializer.deserialize_option(OptionVisitor {
+        codec,
+        _result: PhantomData,
+    })
+}
+
+macro_rules! define_module {
+    ($(#[$meta:meta])* $name:ident => $codec:ident) => {
+        $(#[$meta])*
+        pub mod $name {
+            extern crate alloc;
+            use alloc::vec::Vec;
+            use core::convert::TryFrom;
+
+            use ::serde::{Deserializer, Serializer};
+
+            use crate::$codec;
+
+            #[inline]
+            ///Serializes bytes as BASE64 string.
+            pub fn serialize<T: AsRef<[u8]> + ?Sized, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize(&$codec, value, serializer)
+            }
+
+            #[inline]
+            ///Deserializes bytes from BASE64 string.
+            pub fn deserialize<'de, T: TryFrom<Vec<u8>>, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+                super::deserialize(&$codec, deserializer)
+            }
+
+            pub mod option {
+                //!Serializes optional bytes as BASE64 string or none.
+
+                extern crate alloc;
+                use alloc::vec::Vec;
+                use core::convert::TryFrom;
+
+                use ::serde::{Deserializer, Serializer};
+
+                use crate::$codec;
+
+                #[inline]
+                ///Serializes optional bytes as BASE64 string or none.
+                pub fn serialize<T: AsRef<[u8]>, S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+                    super::super::serialize_option(&$codec, value, serializer)
+                }
+
+                #[inline]
+                ///Deserializes optional bytes from BASE64 string or none.
+                pub fn deserialize<'de, T: TryFrom<Vec<u8>>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+                    super::super::deserialize_option(&$codec, deserializer)
+                }
+            }
+        }
+    };
+}
+
+define_module!(
+    ///Serializes bytes as BASE64 string, using [STANDARD_CODEC](crate::STANDARD_CODEC).
+    standard => STANDARD_CODEC
+);
+define_module!(
+    ///Serializes bytes as BASE64 string, using [URL_CODEC](crate::URL_CODEC).
+    url_safe => URL_CODEC
+);
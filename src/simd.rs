@@ -0,0 +1,381 @@
+//! x86_64 SIMD kernels for built-in alphabets
+//!
+//!Encoding follows Muła's reshuffle with Lemire's lookup, decoding validates characters by ranges
+//!and packs them with `pmaddubsw`/`pmaddwd`.
+//!
+//!Hex is encoded with nibble lookup via `pshufb` and decoded the same way as BASE64, packing pairs with `pmaddubsw`.
+
+use core::arch::x86_64::*;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use super::{STANDARD_TABLE, URL_TABLE};
+
+const LEVEL_UNKNOWN: u8 = 0;
+const LEVEL_NONE: u8 = 1;
+const LEVEL_SSSE3: u8 = 2;
+const LEVEL_AVX2: u8 = 3;
+
+static LEVEL: AtomicU8 = AtomicU8::new(LEVEL_UNKNOWN);
+
+#[cold]
+#[inline(never)]
+fn detect_level() -> u8 {
+    let level = if std::is_x86_feature_detected!("avx2") {
+        LEVEL_AVX2
+    } else if std::is_x86_feature_detected!("ssse3") {
+        LEVEL_SSSE3
+    } else {
+        LEVEL_NONE
+    };
+
+    LEVEL.store(level, Ordering::Relaxed);
+    level
+}
+
+#[inline(always)]
+fn level() -> u8 {
+    match LEVEL.load(Ordering::Relaxed) {
+        LEVEL_UNKNOWN => detect_level(),
+        level => level,
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Alphabet {
+    char62: u8,
+    char63: u8,
+}
+
+impl Alphabet {
+    #[inline(always)]
+    fn from_table(table: &[u8; 64]) -> Option<Self> {
+        if table == STANDARD_TABLE {
+            Some(Self {
+                char62: b'+',
+                char63: b'/',
+            })
+        } else if table == URL_TABLE {
+            Some(Self {
+                char62: b'-',
+                char63: b'_',
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn encode_shift(self) -> [i8; 16] {
+        [
+            (b'a' - 26) as i8, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4,
+            (self.char62 as i8).wrapping_sub(62),
+            (self.char63 as i8).wrapping_sub(63),
+            b'A' as i8, 0, 0,
+        ]
+    }
+}
+
+///Encodes as many full chunks as possible, returning number of consumed bytes.
+///
+///Output size is always `consumed / 3 * 4`.
+pub(crate) fn encode(table: &[u8; 64], src: &[u8], dst: *mut u8) -> usize {
+    let alphabet = match Alphabet::from_table(table) {
+        Some(alphabet) => alphabet,
+        None => return 0,
+    };
+
+    match level() {
+        LEVEL_AVX2 => unsafe {
+            let consumed = encode_avx2(alphabet, src, dst);
+            consumed + encode_ssse3(alphabet, &src[consumed..], dst.add(consumed / 3 * 4))
+        },
+        LEVEL_SSSE3 => unsafe {
+            encode_ssse3(alphabet, src, dst)
+        },
+        _ => 0,
+    }
+}
+
+///Decodes as many full chunks as possible, returning number of consumed characters.
+///
+///Stops at first chunk containing character outside of alphabet.
+///Output size is always `consumed / 4 * 3` and never exceeds `capacity`.
+pub(crate) fn decode(table: &[u8; 64], src: &[u8], dst: *mut u8, capacity: usize) -> usize {
+    let alphabet = match Alphabet::from_table(table) {
+        Some(alphabet) => alphabet,
+        None => return 0,
+    };
+
+    match level() {
+        LEVEL_AVX2 => unsafe {
+            let consumed = decode_avx2(alphabet, src, dst, capacity);
+            let written = consumed / 4 * 3;
+            consumed + decode_ssse3(alphabet, &src[consumed..], dst.add(written), capacity - written)
+        },
+        LEVEL_SSSE3 => unsafe {
+            decode_ssse3(alphabet, src, dst, capacity)
+        },
+        _ => 0,
+    }
+}
+
+#[target_feature(enable = "ssse3")]
+unsafe fn encode_ssse3(alphabet: Alphabet, src: &[u8], dst: *mut u8) -> usize {
+    let shuffle = _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10);
+    let shift = _mm_loadu_si128(alphabet.encode_shift().as_ptr() as *const __m128i);
+
+    let mut consumed = 0;
+    let mut cursor = dst;
+    //Each step reads 16 bytes, but uses only 12
+    while src.len() - consumed >= 16 {
+        let input = _mm_loadu_si128(src.as_ptr().add(consumed) as *const __m128i);
+        let input = _mm_shuffle_epi8(input, shuffle);
+
+        //Spread 3 bytes into 4 6-bit indices within each 32-bit lane
+        let t0 = _mm_and_si128(input, _mm_set1_epi32(0x0fc0fc00));
+        let t1 = _mm_mulhi_epu16(t0, _mm_set1_epi32(0x04000040));
+        let t2 = _mm_and_si128(input, _mm_set1_epi32(0x003f03f0));
+        let t3 = _mm_mullo_epi16(t2, _mm_set1_epi32(0x01000010));
+        let indices = _mm_or_si128(t1, t3);
+
+        //Map index to range in shift table
+        let reduced = _mm_subs_epu8(indices, _mm_set1_epi8(51));
+        let less = _mm_cmpgt_epi8(_mm_set1_epi8(26), indices);
+        let reduced = _mm_or_si128(reduced, _mm_and_si128(less, _mm_set1_epi8(13)));
+        let output = _mm_add_epi8(indices, _mm_shuffle_epi8(shift, reduced));
+
+        _mm_storeu_si128(cursor as *mut __m128i, output);
+        cursor = cursor.add(16);
+        consumed += 12;
+    }
+
+    consumed
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn encode_avx2(alphabet: Alphabet, src: &[u8], dst: *mut u8) -> usize {
+    let shuffle = _mm256_setr_epi8(
+        1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10,
+        1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10,
+    );
+    let shift = _mm256_broadcastsi128_si256(_mm_loadu_si128(alphabet.encode_shift().as_ptr() as *const __m128i));
+
+    let mut consumed = 0;
+    let mut cursor = dst;
+    //Each step reads 12 + 16 bytes, but uses only 24
+    while src.len() - consumed >= 28 {
+        let ptr = src.as_ptr().add(consumed);
+        let lo = _mm_loadu_si128(ptr as *const __m128i);
+        let hi = _mm_loadu_si128(ptr.add(12) as *const __m128i);
+        let input = _mm256_inserti128_si256(_mm256_castsi128_si256(lo), hi, 1);
+        let input = _mm256_shuffle_epi8(input, shuffle);
+
+        let t0 = _mm256_and_si256(input, _mm256_set1_epi32(0x0fc0fc00));
+        let t1 = _mm256_mulhi_epu16(t0, _mm256_set1_epi32(0x04000040));
+        let t2 = _mm256_and_si256(input, _mm256_set1_epi32(0x003f03f0));
+        let t3 = _mm256_mullo_epi16(t2, _mm256_set1_epi32(0x01000010));
+        let indices = _mm256_or_si256(t1, t3);
+
+        let reduced = _mm256_subs_epu8(indices, _mm256_set1_epi8(51));
+        let less = _mm256_cmpgt_epi8(_mm256_set1_epi8(26), indices);
+        let reduced = _mm256_or_si256(reduced, _mm256_and_si256(less, _mm256_set1_epi8(13)));
+        let output = _mm256_add_epi8(indices, _mm256_shuffle_epi8(shift, reduced));
+
+        _mm256_storeu_si256(cursor as *mut __m256i, output);
+        cursor = cursor.add(32);
+        consumed += 24;
+    }
+
+    consumed
+}
+
+#[target_feature(enable = "ssse3")]
+unsafe fn decode_ssse3(alphabet: Alphabet, src: &[u8], dst: *mut u8, capacity: usize) -> usize {
+    let mut consumed = 0;
+    let mut written = 0;
+    //Each step writes 16 bytes, but only 12 are meaningful
+    while src.len() - consumed >= 16 && capacity - written >= 16 {
+        let input = _mm_loadu_si128(src.as_ptr().add(consumed) as *const __m128i);
+
+        //Signed comparison rejects non-ASCII bytes
+        let upper = _mm_and_si128(_mm_cmpgt_epi8(input, _mm_set1_epi8(b'A' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'Z' as i8 + 1), input));
+        let lower = _mm_and_si128(_mm_cmpgt_epi8(input, _mm_set1_epi8(b'a' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'z' as i8 + 1), input));
+        let digit = _mm_and_si128(_mm_cmpgt_epi8(input, _mm_set1_epi8(b'0' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'9' as i8 + 1), input));
+        let char62 = _mm_cmpeq_epi8(input, _mm_set1_epi8(alphabet.char62 as i8));
+        let char63 = _mm_cmpeq_epi8(input, _mm_set1_epi8(alphabet.char63 as i8));
+
+        let valid = _mm_or_si128(_mm_or_si128(_mm_or_si128(upper, lower), _mm_or_si128(digit, char62)), char63);
+        if _mm_movemask_epi8(valid) != 0xffff {
+            break;
+        }
+
+        let shift = _mm_or_si128(
+            _mm_or_si128(
+                _mm_and_si128(upper, _mm_set1_epi8(-(b'A' as i8))),
+                _mm_and_si128(lower, _mm_set1_epi8(26 - b'a' as i8)),
+            ),
+            _mm_or_si128(
+                _mm_and_si128(digit, _mm_set1_epi8(52 - b'0' as i8)),
+                _mm_or_si128(
+                    _mm_and_si128(char62, _mm_set1_epi8(62i8.wrapping_sub(alphabet.char62 as i8))),
+                    _mm_and_si128(char63, _mm_set1_epi8(63i8.wrapping_sub(alphabet.char63 as i8))),
+                ),
+            ),
+        );
+        let values = _mm_add_epi8(input, shift);
+
+        //Merge 4 6-bit values into 3 bytes within each 32-bit lane
+        let merged = _mm_maddubs_epi16(values, _mm_set1_epi32(0x01400140));
+        let merged = _mm_madd_epi16(merged, _mm_set1_epi32(0x00011000));
+        let output = _mm_shuffle_epi8(merged, _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1));
+
+        _mm_storeu_si128(dst.add(written) as *mut __m128i, output);
+        written += 12;
+        consumed += 16;
+    }
+
+    consumed
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn decode_avx2(alphabet: Alphabet, src: &[u8], dst: *mut u8, capacity: usize) -> usize {
+    let mut consumed = 0;
+    let mut written = 0;
+    //Each step writes 32 bytes, but only 24 are meaningful
+    while src.len() - consumed >= 32 && capacity - written >= 32 {
+        let input = _mm256_loadu_si256(src.as_ptr().add(consumed) as *const __m256i);
+
+        let upper = _mm256_and_si256(_mm256_cmpgt_epi8(input, _mm256_set1_epi8(b'A' as i8 - 1)), _mm256_cmpgt_epi8(_mm256_set1_epi8(b'Z' as i8 + 1), input));
+        let lower = _mm256_and_si256(_mm256_cmpgt_epi8(input, _mm256_set1_epi8(b'a' as i8 - 1)), _mm256_cmpgt_epi8(_mm256_set1_epi8(b'z' as i8 + 1), input));
+        let digit = _mm256_and_si256(_mm256_cmpgt_epi8(input, _mm256_set1_epi8(b'0' as i8 - 1)), _mm256_cmpgt_epi8(_mm256_set1_epi8(b'9' as i8 + 1), input));
+        let char62 = _mm256_cmpeq_epi8(input, _mm256_set1_epi8(alphabet.char62 as i8));
+        let char63 = _mm256_cmpeq_epi8(input, _mm256_set1_epi8(alphabet.char63 as i8));
+
+        let valid = _mm256_or_si256(_mm256_or_si256(_mm256_or_si256(upper, lower), _mm256_or_si256(digit, char62)), char63);
+        if _mm256_movemask_epi8(valid) != -1 {
+            break;
+        }
+
+        let shift = _mm256_or_si256(
+            _mm256_or_si256(
+                _mm256_and_si256(upper, _mm256_set1_epi8(-(b'A' as i8))),
+                _mm256_and_si256(lower, _mm256_set1_epi8(26 - b'a' as i8)),
+            ),
+            _mm256_or_si256(
+                _mm256_and_si256(digit, _mm256_set1_epi8(52 - b'0' as i8)),
+                _mm256_or_si256(
+                    _mm256_and_si256(char62, _mm256_set1_epi8(62i8.wrapping_sub(alphabet.char62 as i8))),
+                    _mm256_and_si256(char63, _mm256_set1_epi8(63i8.wrapping_sub(alphabet.char63 as i8))),
+                ),
+            ),
+        );
+        let values = _mm256_add_epi8(input, shift);
+
+        let merged = _mm256_maddubs_epi16(values, _mm256_set1_epi32(0x01400140));
+        let merged = _mm256_madd_epi16(merged, _mm256_set1_epi32(0x00011000));
+        let output = _mm256_shuffle_epi8(merged, _mm256_setr_epi8(
+            2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1,
+            2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1,
+        ));
+        //Join 12 bytes of each lane
+        let output = _mm256_permutevar8x32_epi32(output, _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 7, 7));
+
+        _mm256_storeu_si256(dst.add(written) as *mut __m256i, output);
+        written += 24;
+        consumed += 32;
+    }
+
+    consumed
+}
+
+///Encodes as many 16 byte chunks as possible into hex, returning number of consumed bytes.
+///
+///Output size is always `consumed * 2`.
+pub(crate) fn encode_hex(table: &[u8; 16], src: &[u8], dst: *mut u8) -> usize {
+    match level() {
+        LEVEL_AVX2 | LEVEL_SSSE3 => unsafe {
+            encode_hex_ssse3(table, src, dst)
+        },
+        _ => 0,
+    }
+}
+
+///Decodes as many 32 character chunks of hex as possible, returning number of consumed characters.
+///
+///Stops at first chunk containing character outside of hex digits of either case.
+///Output size is always `consumed / 2`.
+pub(crate) fn decode_hex(src: &[u8], dst: *mut u8) -> usize {
+    match level() {
+        LEVEL_AVX2 | LEVEL_SSSE3 => unsafe {
+            decode_hex_ssse3(src, dst)
+        },
+        _ => 0,
+    }
+}
+
+#[target_feature(enable = "ssse3")]
+unsafe fn encode_hex_ssse3(table: &[u8; 16], src: &[u8], dst: *mut u8) -> usize {
+    let table = _mm_loadu_si128(table.as_ptr() as *const __m128i);
+    let mask = _mm_set1_epi8(0x0f);
+
+    let mut consumed = 0;
+    let mut cursor = dst;
+    while src.len() - consumed >= 16 {
+        let input = _mm_loadu_si128(src.as_ptr().add(consumed) as *const __m128i);
+
+        let hi = _mm_shuffle_epi8(table, _mm_and_si128(_mm_srli_epi16(input, 4), mask));
+        let lo = _mm_shuffle_epi8(table, _mm_and_si128(input, mask));
+
+        _mm_storeu_si128(cursor as *mut __m128i, _mm_unpacklo_epi8(hi, lo));
+        _mm_storeu_si128(cursor.add(16) as *mut __m128i, _mm_unpackhi_epi8(hi, lo));
+        cursor = cursor.add(32);
+        consumed += 16;
+    }
+
+    consumed
+}
+
+#[target_feature(enable = "ssse3")]
+#[inline]
+///Converts hex digits into values, returning `None` if any character is not hex digit.
+unsafe fn hex_values_ssse3(input: __m128i) -> Option<__m128i> {
+    //Signed comparison rejects non-ASCII bytes
+    let digit = _mm_and_si128(_mm_cmpgt_epi8(input, _mm_set1_epi8(b'0' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'9' as i8 + 1), input));
+    let upper = _mm_and_si128(_mm_cmpgt_epi8(input, _mm_set1_epi8(b'A' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'F' as i8 + 1), input));
+    let lower = _mm_and_si128(_mm_cmpgt_epi8(input, _mm_set1_epi8(b'a' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'f' as i8 + 1), input));
+
+    let valid = _mm_or_si128(_mm_or_si128(digit, upper), lower);
+    if _mm_movemask_epi8(valid) != 0xffff {
+        return None;
+    }
+
+    let shift = _mm_or_si128(
+        _mm_and_si128(digit, _mm_set1_epi8(-(b'0' as i8))),
+        _mm_or_si128(
+            _mm_and_si128(upper, _mm_set1_epi8(10 - b'A' as i8)),
+            _mm_and_si128(lower, _mm_set1_epi8(10 - b'a' as i8)),
+        ),
+    );
+    Some(_mm_add_epi8(input, shift))
+}
+
+#[target_feature(enable = "ssse3")]
+unsafe fn decode_hex_ssse3(src: &[u8], dst: *mut u8) -> usize {
+    //Merges pair of values into byte within each 16-bit lane
+    let weights = _mm_set1_epi16(0x0110);
+
+    let mut consumed = 0;
+    while src.len() - consumed >= 32 {
+        let ptr = src.as_ptr().add(consumed);
+        let (lo, hi) = match (hex_values_ssse3(_mm_loadu_si128(ptr as *const __m128i)), hex_values_ssse3(_mm_loadu_si128(ptr.add(16) as *const __m128i))) {
+            (Some(lo), Some(hi)) => (lo, hi),
+            _ => break,
+        };
+
+        let output = _mm_packus_epi16(_mm_maddubs_epi16(lo, weights), _mm_maddubs_epi16(hi, weights));
+        _mm_storeu_si128(dst.add(consumed / 2) as *mut __m128i, output);
+        consumed += 32;
+    }
+
+    consumed
+}
@@ -0,0 +1,301 @@
+//! Incremental encoder and decoder, for data arriving in fragments.
+//!
+//!Neither requires heap, state is limited to single incomplete group.
+
+use core::cmp;
+use core::ptr::NonNull;
+
+use super::{Codec, DecodeError, Padding, PAD, decode_len};
+use super::raw::{decode_bulk, wrap_lines};
+
+///Incremental encoder.
+///
+///Input is buffered until it forms complete 3 byte group, hence [finish](Encoder::finish)
+///must be called at the end to write last group and padding.
+///
+///Line wrapping of [Codec](Codec) is applied across calls, as if whole input is encoded at once.
+#[derive(Clone, Copy)]
+pub struct Encoder<'a> {
+    codec: &'a Codec<'a>,
+    //Incomplete group of input bytes
+    pending: [u8; 3],
+    pending_len: usize,
+    //Number of characters written in current line
+    column: usize,
+}
+
+impl<'a> Encoder<'a> {
+    #[inline]
+    ///Creates new encoder.
+    pub const fn new(codec: &'a Codec<'a>) -> Self {
+        Self {
+            codec,
+            pending: [0; 3],
+            pending_len: 0,
+            column: 0,
+        }
+    }
+
+    #[inline]
+    ///Encodes as much of `input` as fits into `output`.
+    ///
+    ///Returns tuple of consumed bytes and written bytes.
+    ///Output is only written in complete groups of 4 characters, so `output` must fit at least one group to make progress.
+    pub fn push(&mut self, input: &[u8], output: &mut [u8]) -> (usize, usize) {
+        let mut consumed = 0;
+        let mut written = 0;
+
+        if self.pending_len > 0 {
+            let missing = 3 - self.pending_len;
+            if input.len() < missing {
+                self.pending[self.pending_len..self.pending_len + input.len()].copy_from_slice(input);
+                self.pending_len += input.len();
+                return (input.len(), 0);
+            } else if self.fit_len(output.len()) < 4 {
+                return (0, 0);
+            }
+
+            self.pending[self.pending_len..].copy_from_slice(&input[..missing]);
+            let pending = self.pending;
+            written = self.encode(&pending, output);
+            self.pending_len = 0;
+            consumed = missing;
+        }
+
+        let rest = &input[consumed..];
+        let len = cmp::min(rest.len() / 3, self.fit_len(output.len() - written) / 4) * 3;
+        written += self.encode(&rest[..len], &mut output[written..]);
+        consumed += len;
+
+        let rest = &rest[len..];
+        if rest.len() < 3 {
+            self.pending[..rest.len()].copy_from_slice(rest);
+            self.pending_len = rest.len();
+            consumed += rest.len();
+        }
+
+        (consumed, written)
+    }
+
+    #[inline]
+    ///Writes remaining input with padding, resetting encoder.
+    ///
+    ///Returns `None` if `output` is too small, in which case state is preserved.
+    pub fn finish(&mut self, output: &mut [u8]) -> Option<usize> {
+        let len = self.codec.unwrapped_encode_len(self.pending_len);
+        if self.fit_len(output.len()) < len {
+            return None;
+        }
+
+        let pending = self.pending;
+        let size = self.encode(&pending[..self.pending_len], output);
+        *self = Self::new(self.codec);
+        Some(size)
+    }
+
+    #[inline(always)]
+    ///Returns number of characters, that can be written into `size` bytes.
+    fn fit_len(&self, size: usize) -> usize {
+        match &self.codec.line_wrap {
+            Some(line_wrap) => line_wrap.fit_len(self.column, size),
+            None => size,
+        }
+    }
+
+    #[inline(always)]
+    ///Encodes input, that must fit `output`, returning number of written bytes.
+    fn encode(&mut self, input: &[u8], output: &mut [u8]) -> usize {
+        let mut len = output.len();
+        let dst = unsafe {
+            NonNull::new_unchecked(output.as_mut_ptr())
+        };
+        self.codec.encode_unwrapped(input, dst, &mut len);
+
+        match &self.codec.line_wrap {
+            Some(line_wrap) => {
+                let size = unsafe {
+                    wrap_lines(line_wrap, dst.as_ptr(), len, self.column)
+                };
+                self.column = line_wrap.next_column(self.column, len);
+                size
+            },
+            None => len,
+        }
+    }
+}
+
+///Incremental decoder.
+///
+///Input is buffered until it forms complete group of 4 characters, hence [finish](Decoder::finish)
+///must be called at the end to decode last incomplete group.
+///
+///Decoding follows rules of provided [Codec](Codec), with error offsets relative to the start of stream.
+///Group containing padding ends the stream: in strict mode or with [Required](super::Padding::Required) padding any input after it is error, otherwise it is ignored.
+///
+///Decoder should not be used after error.
+#[derive(Clone, Copy)]
+pub struct Decoder<'a> {
+    codec: &'a Codec<'a>,
+    //Incomplete group of input characters with their offsets
+    pending: [u8; 4],
+    pending_offsets: [usize; 4],
+    pending_len: usize,
+    //Number of consumed characters
+    offset: usize,
+    //Padding is seen
+    finished: bool,
+}
+
+impl<'a> Decoder<'a> {
+    #[inline]
+    ///Creates new decoder.
+    pub const fn new(codec: &'a Codec<'a>) -> Self {
+        Self {
+            codec,
+            pending: [0; 4],
+            pending_offsets: [0; 4],
+            pending_len: 0,
+            offset: 0,
+            finished: false,
+        }
+    }
+
+    #[inline]
+    ///Decodes as much of `input` as fits into `output`.
+    ///
+    ///Returns tuple of consumed characters and written bytes.
+    ///Output is written in complete groups of up to 3 bytes, so `output` must fit at least one group to make progress.
+    pub fn push(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize), DecodeError> {
+        unsafe {
+            self.push_raw(input, output.as_mut_ptr(), output.len())
+        }
+    }
+
+    #[inline]
+    ///Decodes remaining input, resetting decoder.
+    ///
+    ///Returns `Err` if remaining input is invalid or `output` is too small, in which case state is preserved.
+    pub fn finish(&mut self, output: &mut [u8]) -> Result<usize, DecodeError> {
+        unsafe {
+            self.finish_raw(output.as_mut_ptr(), output.len())
+        }
+    }
+
+    ///Raw version of [push](Decoder::push).
+    ///
+    ///# Safety
+    ///
+    ///`dst` must be valid for writes of `capacity` bytes.
+    pub(crate) unsafe fn push_raw(&mut self, input: &[u8], dst: *mut u8, capacity: usize) -> Result<(usize, usize), DecodeError> {
+        let mut consumed = 0;
+        let mut written = 0;
+
+        loop {
+            if self.finished {
+                let rest = &input[consumed..];
+                if self.codec.strict || self.codec.padding == Padding::Required {
+                    if let Some(pos) = rest.iter().position(|ch| !self.codec.is_skipped(*ch)) {
+                        self.offset += pos;
+                        return Err(DecodeError::InvalidPadding);
+                    }
+                }
+                self.offset += rest.len();
+                return Ok((input.len(), written));
+            }
+
+            if self.pending_len == 0 {
+                while consumed < input.len() && self.codec.is_skipped(input[consumed]) {
+                    consumed += 1;
+                    self.offset += 1;
+                }
+
+                let rest = &input[consumed..];
+                let limit = cmp::min(rest.len() / 4, (capacity - written) / 3) * 4;
+                //Accelerated path stops before anything but characters from the table
+                let mut len = decode_bulk(self.codec.table, &self.codec.reverse, &rest[..limit], dst.add(written), capacity - written);
+                written += len / 4 * 3;
+
+                //Group with padding must be decoded alone, as the last one
+                let tail = &rest[len..limit];
+                let tail_len = match tail.iter().position(|ch| *ch == PAD || self.codec.is_skipped(*ch)) {
+                    Some(pos) => pos / 4 * 4,
+                    None => tail.len(),
+                };
+                if tail_len > 0 {
+                    written += self.decode(&tail[..tail_len], dst.add(written), capacity - written).map_err(|error| error.with_base_offset(self.offset + len))?;
+                    len += tail_len;
+                }
+
+                if len > 0 {
+                    consumed += len;
+                    self.offset += len;
+                    continue;
+                }
+            }
+
+            while self.pending_len < 4 && consumed < input.len() {
+                let ch = input[consumed];
+                if !self.codec.is_skipped(ch) {
+                    self.pending[self.pending_len] = ch;
+                    self.pending_offsets[self.pending_len] = self.offset;
+                    self.pending_len += 1;
+                }
+                consumed += 1;
+                self.offset += 1;
+            }
+
+            if self.pending_len < 4 {
+                return Ok((consumed, written));
+            }
+
+            match self.decode_pending(dst.add(written), capacity - written) {
+                Ok(size) => {
+                    written += size;
+                    self.finished = self.pending.contains(&PAD);
+                    self.pending_len = 0;
+                },
+                Err(DecodeError::OutputTooSmall { .. }) => return Ok((consumed, written)),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    ///Raw version of [finish](Decoder::finish).
+    ///
+    ///# Safety
+    ///
+    ///`dst` must be valid for writes of `capacity` bytes.
+    pub(crate) unsafe fn finish_raw(&mut self, dst: *mut u8, capacity: usize) -> Result<usize, DecodeError> {
+        let size = match self.finished {
+            true => 0,
+            false => self.decode_pending(dst, capacity)?,
+        };
+        *self = Self::new(self.codec);
+        Ok(size)
+    }
+
+    ///Decodes pending characters, mapping error offset to the stream.
+    unsafe fn decode_pending(&self, dst: *mut u8, capacity: usize) -> Result<usize, DecodeError> {
+        match self.decode(&self.pending[..self.pending_len], dst, capacity) {
+            Ok(size) => Ok(size),
+            Err(DecodeError::InvalidByte { offset, byte }) => Err(DecodeError::InvalidByte {
+                offset: self.pending_offsets[offset],
+                byte,
+            }),
+            Err(error) => Err(error),
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn decode(&self, input: &[u8], dst: *mut u8, capacity: usize) -> Result<usize, DecodeError> {
+        let mut len = decode_len(input);
+        if len > capacity {
+            return Err(DecodeError::OutputTooSmall {
+                required: len,
+            });
+        }
+
+        self.codec.decode_plain(input, NonNull::new_unchecked(dst), &mut len)?;
+        Ok(len)
+    }
+}
@@ -40,7 +40,7 @@ impl<'a> Codec<'a> {
     ///In case of required size to be too big or table contains non-ASCII characters
     #[inline]
     pub fn encode_into_string(&self, src: &[u8]) -> String {
-        let result = vec::encode(self.table, src);
+        let result = self.encode_into_vec(src);
 
         unsafe {
             String::from_utf8_unchecked(result)
@@ -0,0 +1,197 @@
+//! Portable SWAR implementation, processing 6 bytes as 8 characters within `u64`
+//!
+//!Built-in alphabets are encoded arithmetically, adding per character offset selected by range of its index,
+//!custom alphabets look up every index in table.
+//!
+//!Hex is encoded as 4 bytes into 8 characters and decoded as 16 characters into 8 bytes within `u64`.
+
+use core::ptr;
+
+use super::{REVERSE_TABLE_SIZE, STANDARD_TABLE, URL_TABLE};
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = ONES * 0x80;
+
+#[inline(always)]
+///Adds bytes of both words, wrapping within each byte.
+fn add_bytes(left: u64, right: u64) -> u64 {
+    ((left & !HIGH_BITS) + (right & !HIGH_BITS)) ^ ((left ^ right) & HIGH_BITS)
+}
+
+#[inline(always)]
+///Returns `value` in every byte, where byte of `indices` is at least `threshold`, and zero otherwise.
+///
+///Bytes of `indices` must be less than 64.
+fn select_from(indices: u64, threshold: u8, value: u8) -> u64 {
+    let is_above = ((indices + ONES * (0x80 - threshold) as u64) & HIGH_BITS) >> 7;
+    is_above * value as u64
+}
+
+#[inline(always)]
+///Spreads 6 bytes at the top of `word` into 8 indices, each in its own byte.
+fn spread_indices(word: u64) -> u64 {
+    let word = word >> 16;
+    //Each half of 24 bits goes to its own 32 bits
+    let word = (word >> 24) << 32 | (word & 0xff_ffff);
+    (word << 6 & 0x3f00_0000_3f00_0000)
+        | (word << 4 & 0x003f_0000_003f_0000)
+        | (word << 2 & 0x0000_3f00_0000_3f00)
+        | (word & 0x0000_003f_0000_003f)
+}
+
+#[inline(always)]
+fn encode_blocks(src: &[u8], dst: *mut u8, map: impl Fn(u64) -> u64) -> usize {
+    let mut consumed = 0;
+    let mut cursor = dst;
+
+    //Each step reads 8 bytes, but uses only 6
+    while src.len() - consumed >= 8 {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&src[consumed..consumed + 8]);
+        let output = map(spread_indices(u64::from_be_bytes(word)));
+
+        unsafe {
+            ptr::copy_nonoverlapping(output.to_be_bytes().as_ptr(), cursor, 8);
+            cursor = cursor.add(8);
+        }
+        consumed += 6;
+    }
+
+    consumed
+}
+
+#[inline(always)]
+///Maps indices to characters of built-in alphabet, differing only in last two characters.
+fn map_builtin(indices: u64, char62: u8, char63: u8) -> u64 {
+    //Offset from index to character for A-Z, a-z, 0-9 and the last two characters
+    let mut offset = ONES * b'A' as u64;
+    offset = add_bytes(offset, select_from(indices, 26, (b'a' - 26).wrapping_sub(b'A')));
+    offset = add_bytes(offset, select_from(indices, 52, (b'0'.wrapping_sub(52)).wrapping_sub(b'a' - 26)));
+    offset = add_bytes(offset, select_from(indices, 62, char62.wrapping_sub(62).wrapping_sub(b'0'.wrapping_sub(52))));
+    offset = add_bytes(offset, select_from(indices, 63, char63.wrapping_sub(63).wrapping_sub(char62.wrapping_sub(62))));
+    add_bytes(indices, offset)
+}
+
+///Encodes as many full blocks as possible, returning number of consumed bytes.
+///
+///Output size is always `consumed / 3 * 4`.
+pub(crate) fn encode(table: &[u8; 64], src: &[u8], dst: *mut u8) -> usize {
+    if table == STANDARD_TABLE {
+        encode_blocks(src, dst, |indices| map_builtin(indices, b'+', b'/'))
+    } else if table == URL_TABLE {
+        encode_blocks(src, dst, |indices| map_builtin(indices, b'-', b'_'))
+    } else {
+        encode_blocks(src, dst, |indices| {
+            let mut chars = indices.to_be_bytes();
+            for ch in chars.iter_mut() {
+                *ch = unsafe {
+                    *table.get_unchecked(*ch as usize)
+                };
+            }
+            u64::from_be_bytes(chars)
+        })
+    }
+}
+
+///Decodes as many full blocks as possible, returning number of consumed characters.
+///
+///Stops at first block containing character outside of table.
+///Output size is always `consumed / 4 * 3` and never exceeds `capacity`.
+pub(crate) fn decode(reverse_table: &[i8; REVERSE_TABLE_SIZE], src: &[u8], dst: *mut u8, capacity: usize) -> usize {
+    let mut consumed = 0;
+    let mut written = 0;
+
+    while src.len() - consumed >= 8 && capacity - written >= 6 {
+        let block = &src[consumed..consumed + 8];
+        let mut word = 0u64;
+        //Invalid characters are -1, so their sign bit is accumulated
+        let mut invalid = 0u8;
+
+        for ch in block {
+            let value = unsafe {
+                *reverse_table.get_unchecked(*ch as usize) as u8
+            };
+            invalid |= value;
+            word = word << 6 | value as u64;
+        }
+
+        if invalid & 0x80 != 0 {
+            break;
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping((word << 16).to_be_bytes().as_ptr(), dst.add(written), 6);
+        }
+        written += 6;
+        consumed += 8;
+    }
+
+    consumed
+}
+
+///Encodes as many 4 byte blocks as possible into hex, returning number of consumed bytes.
+///
+///Output size is always `consumed * 2`.
+pub(crate) fn encode_hex(table: &[u8; 16], src: &[u8], dst: *mut u8) -> usize {
+    let mut consumed = 0;
+    let mut cursor = dst;
+
+    while src.len() - consumed >= 4 {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(&src[consumed..consumed + 4]);
+        let word = u32::from_be_bytes(word);
+
+        macro_rules! symbol {
+            ($shift:literal) => {
+                unsafe {
+                    *table.get_unchecked(((word >> $shift) & 0xf) as usize) as u64
+                }
+            }
+        }
+
+        let output = symbol!(28) << 56 | symbol!(24) << 48 | symbol!(20) << 40 | symbol!(16) << 32
+                   | symbol!(12) << 24 | symbol!(8) << 16 | symbol!(4) << 8 | symbol!(0);
+
+        unsafe {
+            ptr::copy_nonoverlapping(output.to_be_bytes().as_ptr(), cursor, 8);
+            cursor = cursor.add(8);
+        }
+        consumed += 4;
+    }
+
+    consumed
+}
+
+///Decodes as many 16 character blocks of hex as possible, returning number of consumed characters.
+///
+///Stops at first block containing character outside of table.
+///Output size is always `consumed / 2`.
+pub(crate) fn decode_hex(reverse_table: &[i8; REVERSE_TABLE_SIZE], src: &[u8], dst: *mut u8) -> usize {
+    let mut consumed = 0;
+
+    while src.len() - consumed >= 16 {
+        let block = &src[consumed..consumed + 16];
+        let mut word = 0u64;
+        //Invalid characters are -1, so their sign bit is accumulated
+        let mut invalid = 0u8;
+
+        for ch in block {
+            let value = unsafe {
+                *reverse_table.get_unchecked(*ch as usize) as u8
+            };
+            invalid |= value;
+            word = word << 4 | (value & 0xf) as u64;
+        }
+
+        if invalid & 0x80 != 0 {
+            break;
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(word.to_be_bytes().as_ptr(), dst.add(consumed / 2), 8);
+        }
+        consumed += 16;
+    }
+
+    consumed
+}
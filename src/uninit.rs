@@ -2,7 +2,7 @@
 
 use core::{ptr, mem};
 
-use super::Codec;
+use super::{Codec, DecodeError};
 
 ///Encoding function writing to uninit slice.
 ///
@@ -36,19 +36,19 @@ pub fn encode(table: &[u8; 64], src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) ->
 ///
 ///# Result
 ///
-/// Returns `Some` if successful, containing number of bytes written.
+/// Returns `Ok` if successful, containing number of bytes written.
 ///
-/// Returns `None` if data cannot be encoded due to insufficient buffer size or invalid input.
+/// Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
 #[inline]
-pub fn decode(table: &[u8; 64], src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Option<usize> {
+pub fn decode(table: &[u8; 64], src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Result<usize, DecodeError> {
     let mut len = dst.len();
     let dst = unsafe {
         ptr::NonNull::new_unchecked(dst.as_mut_ptr() as *mut u8)
     };
-    match unsafe { super::raw::decode(table, src, dst, &mut len) } {
-        true => Some(len),
-        false => None,
+    unsafe {
+        super::raw::decode(table, src, dst, &mut len)?;
     }
+    Ok(len)
 }
 
 impl<'a> Codec<'a> {
@@ -63,9 +63,16 @@ impl<'a> Codec<'a> {
     ///Returns `Some` if successful, containing number of bytes written.
     ///
     ///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
-    #[inline(always)]
+    #[inline]
     pub fn encode_to_uninit(self, src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Option<usize> {
-        encode(self.table, src, dst)
+        let mut len = dst.len();
+        let dst = unsafe {
+            ptr::NonNull::new_unchecked(dst.as_mut_ptr() as *mut u8)
+        };
+        match unsafe { self.encode_to_raw(src, dst, &mut len) } {
+            true => Some(len),
+            false => None,
+        }
     }
 
     ///Decoding function writing to uninit slice.
@@ -77,18 +84,18 @@ impl<'a> Codec<'a> {
     ///
     ///# Result
     ///
-    /// Returns `Some` if successful, containing number of bytes written.
+    /// Returns `Ok` if successful, containing number of bytes written.
     ///
-    /// Returns `None` if data cannot be encoded due to insufficient buffer size or invalid input.
+    /// Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
     #[inline]
-    pub fn decode_to_uninit(&self, src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Option<usize> {
+    pub fn decode_to_uninit(&self, src: &[u8], dst: &mut [mem::MaybeUninit<u8>]) -> Result<usize, DecodeError> {
         let mut len = dst.len();
         let dst = unsafe {
             ptr::NonNull::new_unchecked(dst.as_mut_ptr() as *mut u8)
         };
-        match unsafe { self.decode_to_raw(src, dst, &mut len) } {
-            true => Some(len),
-            false => None,
+        unsafe {
+            self.decode_to_raw(src, dst, &mut len)?;
         }
+        Ok(len)
     }
 }
@@ -3,7 +3,7 @@
 extern crate alloc;
 use alloc::vec::Vec;
 
-use super::{Codec, encode_len, decode_len, raw};
+use super::{Codec, DecodeError, encode_len, decode_len, raw};
 
 ///Encoding function returns vector with data written.
 ///
@@ -25,7 +25,7 @@ pub fn encode(table: &[u8; 64], src: &[u8]) -> Vec<u8> {
     let mut result = Vec::with_capacity(required_len);
     unsafe {
         let ptr = core::ptr::NonNull::new_unchecked(result.as_mut_ptr());
-        raw::encode_inner(table, src, ptr, &mut required_len);
+        raw::encode_inner(table, src, ptr, &mut required_len, true);
         result.set_len(required_len);
     }
 
@@ -42,24 +42,20 @@ pub fn encode(table: &[u8; 64], src: &[u8]) -> Vec<u8> {
 ///
 ///# Result
 ///
-///Returns `Some` if successful, containing decoded output
+///Returns `Ok` if successful, containing decoded output
 ///
-///Returns `None` if `src` is invalid input.
+///Returns `Err` if `src` is invalid input.
 #[inline]
-pub fn decode(table: &[u8; 64], src: &[u8]) -> Option<Vec<u8>> {
+pub fn decode(table: &[u8; 64], src: &[u8]) -> Result<Vec<u8>, DecodeError> {
     let mut required_len = decode_len(src);
     let mut result = Vec::with_capacity(required_len);
     unsafe {
         let ptr = core::ptr::NonNull::new_unchecked(result.as_mut_ptr());
-        match raw::decode_inner(table, src, ptr, &mut required_len) {
-            true => {
-                result.set_len(required_len);
-            },
-            false => return None,
-        }
+        raw::decode_inner(table, src, ptr, &mut required_len)?;
+        result.set_len(required_len);
     }
 
-    Some(result)
+    Ok(result)
 }
 
 impl<'a> Codec<'a> {
@@ -73,9 +69,21 @@ impl<'a> Codec<'a> {
     ///# Panics
     ///
     ///In case of required size to be too big
-    #[inline(always)]
+    #[inline]
     pub fn encode_into_vec(&self, src: &[u8]) -> Vec<u8> {
-        encode(self.table, src)
+        let mut required_len = self.encode_len(src.len());
+        //Make sure that we don't overflow (which is unlikely but still)
+        //>= for case of zero sized input
+        assert!(required_len >= src.len());
+
+        let mut result = Vec::with_capacity(required_len);
+        unsafe {
+            let ptr = core::ptr::NonNull::new_unchecked(result.as_mut_ptr());
+            self.encode_inner(src, ptr, &mut required_len);
+            result.set_len(required_len);
+        }
+
+        result
     }
 
     ///Decoding function returns vector with data written.
@@ -88,23 +96,30 @@ impl<'a> Codec<'a> {
     ///
     ///# Result
     ///
-    ///Returns `Some` if successful, containing decoded output
+    ///Returns `Ok` if successful, containing decoded output
     ///
-    ///Returns `None` if `src` is invalid input.
+    ///Returns `Err` if `src` is invalid input.
     #[inline]
-    pub fn decode_into_vec(&self, src: &[u8]) -> Option<Vec<u8>> {
+    pub fn decode_into_vec(&self, src: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        //Skipped characters are counted, so it is upper bound
         let mut required_len = decode_len(src);
         let mut result = Vec::with_capacity(required_len);
         unsafe {
             let ptr = core::ptr::NonNull::new_unchecked(result.as_mut_ptr());
-            match raw::decode_inner_with_rev(&self.reverse, src, ptr, &mut required_len) {
-                true => {
-                    result.set_len(required_len);
+            match self.decode_inner(src, ptr, &mut required_len) {
+                Ok(()) => (),
+                //Except for malformed padding with skipped characters
+                Err(DecodeError::OutputTooSmall { required }) => {
+                    result.reserve_exact(required);
+                    required_len = required;
+                    let ptr = core::ptr::NonNull::new_unchecked(result.as_mut_ptr());
+                    self.decode_inner(src, ptr, &mut required_len)?;
                 },
-                false => return None,
+                Err(error) => return Err(error),
             }
+            result.set_len(required_len);
         }
 
-        Some(result)
+        Ok(result)
     }
 }
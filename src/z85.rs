@@ -0,0 +1,376 @@
+//! Z85 codec, as specified by ZeroMQ RFC 32
+//!
+//!Every 4 bytes of input are encoded as 5 characters, so input must be multiple of 4 bytes and encoded input multiple of 5 characters.
+//!
+//!Padded variant accepts input of any length, truncating last group as Ascii85 does: `n` trailing bytes are encoded as `n + 1` characters.
+//!
+//!Group, that does not fit 32 bits, is reported as [InvalidByte](DecodeError::InvalidByte) at its last character.
+
+use super::{DecodeError, REVERSE_TABLE_SIZE};
+
+///Z85 alphabet
+pub static TABLE: &[u8; 85] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+///Size of ZeroMQ CURVE key
+pub const KEY_SIZE: usize = 32;
+///Size of encoded ZeroMQ CURVE key
+pub const ENCODED_KEY_SIZE: usize = 40;
+
+static REVERSE_TABLE: [i8; REVERSE_TABLE_SIZE] = build_reverse_table();
+
+const fn build_reverse_table() -> [i8; REVERSE_TABLE_SIZE] {
+    let mut reverse = [-1i8; REVERSE_TABLE_SIZE];
+
+    let mut idx = 0;
+    while idx < TABLE.len() {
+        reverse[TABLE[idx] as usize] = idx as i8;
+        idx += 1;
+    }
+
+    reverse
+}
+
+#[inline(always)]
+const fn encode_group(value: u32) -> [u8; 5] {
+    let mut chars = [0u8; 5];
+    let mut value = value;
+    let mut idx = chars.len();
+    while idx > 0 {
+        idx -= 1;
+        chars[idx] = TABLE[(value % 85) as usize];
+        value /= 85;
+    }
+    chars
+}
+
+///Decodes `len` characters starting at `offset`, with the rest of group filled by the highest digit.
+const fn decode_group(src: &[u8], offset: usize, len: usize) -> Result<u32, DecodeError> {
+    let mut value = 0u64;
+    let mut idx = 0;
+    while idx < 5 {
+        let digit = match idx < len {
+            true => match REVERSE_TABLE[src[offset + idx] as usize] {
+                -1 => return Err(DecodeError::InvalidByte {
+                    offset: offset + idx,
+                    byte: src[offset + idx],
+                }),
+                digit => digit as u64,
+            },
+            false => 84,
+        };
+        value = value * 85 + digit;
+        idx += 1;
+    }
+
+    match value > u32::MAX as u64 {
+        true => Err(DecodeError::InvalidByte {
+            offset: offset + len - 1,
+            byte: src[offset + len - 1],
+        }),
+        false => Ok(value as u32),
+    }
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to encode input of provided size.
+///
+///Returns `None` if input is not multiple of 4 or on overflow.
+pub const fn encode_len(input: usize) -> Option<usize> {
+    match input % 4 {
+        0 => (input / 4).checked_mul(5),
+        _ => None,
+    }
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to decode provided input.
+///
+///Returns `None` if input is not multiple of 5.
+pub const fn decode_len(input: &[u8]) -> Option<usize> {
+    match input.len() % 5 {
+        0 => Some(input.len() / 5 * 4),
+        _ => None,
+    }
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to encode input of provided size with padded variant.
+///
+///On overflow returns wrapped value.
+pub const fn encode_padded_len(input: usize) -> usize {
+    let tail = input % 4;
+    (input / 4).wrapping_mul(5).wrapping_add(tail + (tail != 0) as usize)
+}
+
+#[inline(always)]
+///Returns number of bytes necessary to decode provided input with padded variant.
+///
+///Returns `None` if input has invalid length, i.e. its last group is single character.
+pub const fn decode_padded_len(input: &[u8]) -> Option<usize> {
+    match input.len() % 5 {
+        0 => Some(input.len() / 5 * 4),
+        1 => None,
+        tail => Some(input.len() / 5 * 4 + tail - 1),
+    }
+}
+
+fn encode_inner(src: &[u8], dst: &mut [u8]) -> usize {
+    let mut written = 0;
+    let mut chunks = src.chunks_exact(4);
+    for chunk in chunks.by_ref() {
+        let value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        dst[written..written + 5].copy_from_slice(&encode_group(value));
+        written += 5;
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let mut group = [0u8; 4];
+        group[..tail.len()].copy_from_slice(tail);
+        dst[written..written + tail.len() + 1].copy_from_slice(&encode_group(u32::from_be_bytes(group))[..tail.len() + 1]);
+        written += tail.len() + 1;
+    }
+
+    written
+}
+
+fn decode_inner(src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
+    let mut written = 0;
+    let mut offset = 0;
+    while offset < src.len() {
+        let len = core::cmp::min(src.len() - offset, 5);
+        if len == 1 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let value = decode_group(src, offset, len)?;
+        dst[written..written + len - 1].copy_from_slice(&value.to_be_bytes()[..len - 1]);
+        written += len - 1;
+        offset += len;
+    }
+
+    Ok(written)
+}
+
+///Encoding function writing to slice.
+///
+///# Arguments
+///
+///- `src` - Input to encode, must be multiple of 4 bytes;
+///- `dst` - Output to write;
+///
+///# Result
+///
+///Returns `Some` if successful, containing number of bytes written.
+///
+///Returns `None` if input length is not multiple of 4 or `dst` is too small.
+#[inline]
+pub fn encode_to(src: &[u8], dst: &mut [u8]) -> Option<usize> {
+    match encode_len(src.len()) {
+        Some(required_len) if required_len <= dst.len() => Some(encode_inner(src, dst)),
+        _ => None,
+    }
+}
+
+///Decoding function writing to slice.
+///
+///# Arguments
+///
+///- `src` - Input to decode, must be multiple of 5 characters;
+///- `dst` - Output to write;
+///
+///# Result
+///Returns `Ok` if successful, containing number of bytes written.
+///
+///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
+#[inline]
+pub fn decode_to(src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
+    match decode_len(src) {
+        Some(required_len) if required_len > dst.len() => Err(DecodeError::OutputTooSmall {
+            required: required_len,
+        }),
+        Some(_) => decode_inner(src, dst),
+        None => Err(DecodeError::InvalidLength),
+    }
+}
+
+///Padded encoding function writing to slice.
+///
+///# Arguments
+///
+///- `src` - Input to encode;
+///- `dst` - Output to write;
+///
+///# Result
+///
+///Returns `Some` if successful, containing number of bytes written.
+///
+///Returns `None` if data cannot be encoded due to insufficient buffer size or size calculation overflow happens.
+#[inline]
+pub fn encode_padded_to(src: &[u8], dst: &mut [u8]) -> Option<usize> {
+    let required_len = encode_padded_len(src.len());
+    match required_len >= src.len() && required_len <= dst.len() {
+        true => Some(encode_inner(src, dst)),
+        false => None,
+    }
+}
+
+///Padded decoding function writing to slice.
+///
+///# Arguments
+///
+///- `src` - Input to decode;
+///- `dst` - Output to write;
+///
+///# Result
+///Returns `Ok` if successful, containing number of bytes written.
+///
+///Returns `Err` if data cannot be decoded due to insufficient buffer size or invalid input.
+#[inline]
+pub fn decode_padded_to(src: &[u8], dst: &mut [u8]) -> Result<usize, DecodeError> {
+    match decode_padded_len(src) {
+        Some(required_len) if required_len > dst.len() => Err(DecodeError::OutputTooSmall {
+            required: required_len,
+        }),
+        Some(_) => decode_inner(src, dst),
+        None => Err(DecodeError::InvalidLength),
+    }
+}
+
+///Encodes ZeroMQ CURVE key.
+pub const fn encode_key(key: &[u8; KEY_SIZE]) -> [u8; ENCODED_KEY_SIZE] {
+    let mut result = [0u8; ENCODED_KEY_SIZE];
+    let mut group = 0;
+    while group < KEY_SIZE / 4 {
+        let value = u32::from_be_bytes([key[group * 4], key[group * 4 + 1], key[group * 4 + 2], key[group * 4 + 3]]);
+        let chars = encode_group(value);
+        let mut idx = 0;
+        while idx < chars.len() {
+            result[group * 5 + idx] = chars[idx];
+            idx += 1;
+        }
+        group += 1;
+    }
+    result
+}
+
+///Decodes ZeroMQ CURVE key.
+///
+///# Result
+///
+///Returns `Err` if input is not 40 characters or contains invalid characters.
+pub const fn decode_key(input: &[u8]) -> Result<[u8; KEY_SIZE], DecodeError> {
+    if input.len() != ENCODED_KEY_SIZE {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut result = [0u8; KEY_SIZE];
+    let mut group = 0;
+    while group < KEY_SIZE / 4 {
+        let bytes = match decode_group(input, group * 5, 5) {
+            Ok(value) => value.to_be_bytes(),
+            Err(error) => return Err(error),
+        };
+        let mut idx = 0;
+        while idx < bytes.len() {
+            result[group * 4 + idx] = bytes[idx];
+            idx += 1;
+        }
+        group += 1;
+    }
+    Ok(result)
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impl {
+    extern crate alloc;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::{DecodeError, decode_inner, decode_len, decode_padded_len, encode_inner, encode_len, encode_padded_len};
+
+    ///Encoding function returns string.
+    ///
+    ///Requires feature `alloc`.
+    ///
+    ///# Arguments
+    ///- `src` - Input to encode, must be multiple of 4 bytes;
+    ///
+    ///# Result
+    ///
+    ///Returns `None` if input length is not multiple of 4.
+    #[inline]
+    pub fn encode_into_string(src: &[u8]) -> Option<String> {
+        let mut result = vec![0u8; encode_len(src.len())?];
+        encode_inner(src, &mut result);
+
+        //Table is ASCII
+        Some(unsafe {
+            String::from_utf8_unchecked(result)
+        })
+    }
+
+    ///Decoding function returns vector with data written.
+    ///
+    ///Requires feature `alloc`.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to decode, must be multiple of 5 characters;
+    ///
+    ///# Result
+    ///
+    ///Returns `Err` if `src` is invalid input.
+    #[inline]
+    pub fn decode_into_vec(src: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut result = vec![0u8; decode_len(src).ok_or(DecodeError::InvalidLength)?];
+        decode_inner(src, &mut result)?;
+        Ok(result)
+    }
+
+    ///Padded encoding function returns string.
+    ///
+    ///Requires feature `alloc`.
+    ///
+    ///# Arguments
+    ///- `src` - Input to encode;
+    ///
+    ///# Panics
+    ///
+    ///In case of required size to be too big
+    #[inline]
+    pub fn encode_padded_into_string(src: &[u8]) -> String {
+        let required_len = encode_padded_len(src.len());
+        //>= for case of zero sized input
+        assert!(required_len >= src.len());
+
+        let mut result = vec![0u8; required_len];
+        encode_inner(src, &mut result);
+
+        //Table is ASCII
+        unsafe {
+            String::from_utf8_unchecked(result)
+        }
+    }
+
+    ///Padded decoding function returns vector with data written.
+    ///
+    ///Requires feature `alloc`.
+    ///
+    ///# Arguments
+    ///
+    ///- `src` - Input to decode;
+    ///
+    ///# Result
+    ///
+    ///Returns `Err` if `src` is invalid input.
+    #[inline]
+    pub fn decode_padded_into_vec(src: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut result = vec![0u8; decode_padded_len(src).ok_or(DecodeError::InvalidLength)?];
+        decode_inner(src, &mut result)?;
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use alloc_impl::{encode_into_string, decode_into_vec, encode_padded_into_string, decode_padded_into_vec};
@@ -0,0 +1,181 @@
+//! Verifies accelerated paths against plain implementation.
+//!
+//!Custom table is never SIMD accelerated, so built-in table is compared with its mirrored version,
+//!translating output character by character.
+//!Every table is also compared with naive reference implementation, covering SIMD and SWAR paths of built-in tables
+//!and SWAR table lookup of mirrored ones. Without feature `std` only SWAR paths are used.
+
+use based64::{Codec, DecodeError, STANDARD_TABLE, URL_TABLE, PAD};
+
+const fn mirror(table: &[u8; 64]) -> [u8; 64] {
+    let mut result = [0u8; 64];
+    let mut idx = 0;
+    while idx < 64 {
+        result[idx] = table[63 - idx];
+        idx += 1;
+    }
+    result
+}
+
+static STANDARD_MIRROR: [u8; 64] = mirror(STANDARD_TABLE);
+static URL_MIRROR: [u8; 64] = mirror(URL_TABLE);
+
+struct Pair {
+    table: &'static [u8; 64],
+    mirror: &'static [u8; 64],
+}
+
+impl Pair {
+    fn translate(&self, input: &[u8]) -> Vec<u8> {
+        input.iter().map(|ch| match self.table.iter().position(|table_ch| table_ch == ch) {
+            Some(idx) => self.mirror[idx],
+            None => *ch,
+        }).collect()
+    }
+
+    fn codecs(&self) -> [(Codec<'static>, Codec<'static>); 2] {
+        [
+            (Codec::new(self.table), Codec::new(self.mirror)),
+            (Codec::new(self.table).with_strict(true), Codec::new(self.mirror).with_strict(true)),
+        ]
+    }
+}
+
+const PAIRS: [Pair; 2] = [
+    Pair { table: STANDARD_TABLE, mirror: &STANDARD_MIRROR },
+    Pair { table: URL_TABLE, mirror: &URL_MIRROR },
+];
+
+fn reference_encode(table: &[u8; 64], input: &[u8]) -> Vec<u8> {
+    let mut result = Vec::new();
+    for chunk in input.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (idx, byte)| bits | (*byte as u32) << (16 - idx * 8));
+        for idx in 0..4 {
+            match idx <= chunk.len() {
+                true => result.push(table[(bits >> (18 - idx * 6)) as usize & 0x3f]),
+                false => result.push(PAD),
+            }
+        }
+    }
+    result
+}
+
+fn reference_decode(table: &[u8; 64], input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let data_len = input.iter().rposition(|ch| *ch != PAD).map_or(0, |idx| idx + 1);
+    let mut result = Vec::new();
+    let mut bits = 0u32;
+    let mut bits_len = 0;
+    for (offset, ch) in input[..data_len].iter().enumerate() {
+        let value = table.iter().position(|table_ch| table_ch == ch).ok_or(DecodeError::InvalidByte {
+            offset,
+            byte: *ch,
+        })?;
+        bits = bits << 6 | value as u32;
+        bits_len += 6;
+        if bits_len >= 8 {
+            bits_len -= 8;
+            result.push((bits >> bits_len) as u8);
+        }
+    }
+    Ok(result)
+}
+
+fn random_input(len: usize) -> Vec<u8> {
+    let mut input = vec![0u8; len];
+    getrandom::getrandom(&mut input).expect("Random should work");
+    input
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_match_plain_encode_and_decode() {
+    for pair in PAIRS.iter() {
+        for len in (0..512).chain([4096, 4097, 4098]) {
+            let input = random_input(len);
+
+            let mut encoded = vec![0u8; based64::encode_len(len)];
+            let mut mirror_encoded = vec![0u8; based64::encode_len(len)];
+            let size = based64::encode(pair.table, &input, &mut encoded).expect("to encode");
+            assert_eq!(size, encoded.len());
+            based64::encode(pair.mirror, &input, &mut mirror_encoded).expect("to encode");
+            assert_eq!(pair.translate(&encoded), mirror_encoded, "encode mismatch for len={}", len);
+            assert_eq!(encoded, reference_encode(pair.table, &input), "encode differs from reference for len={}", len);
+            assert_eq!(mirror_encoded, reference_encode(pair.mirror, &input), "mirror encode differs from reference for len={}", len);
+
+            for (codec, mirror_codec) in pair.codecs() {
+                //Exact buffer with guard, to verify nothing is written out of bounds
+                let mut decoded = vec![0xaau8; len + 64];
+                let mut mirror_decoded = vec![0u8; len];
+                assert_eq!(codec.decode_to(&encoded, &mut decoded[..len]), Ok(len), "decode fails for len={}", len);
+                assert_eq!(mirror_codec.decode_to(&mirror_encoded, &mut mirror_decoded), Ok(len));
+                assert_eq!(&decoded[..len], &input[..]);
+                assert_eq!(mirror_decoded, input);
+                assert!(decoded[len..].iter().all(|byte| *byte == 0xaa), "decode writes out of bounds for len={}", len);
+
+                let unpadded = encoded.iter().position(|ch| *ch == PAD).unwrap_or(encoded.len());
+                assert_eq!(codec.with_strict(false).decode_to(&encoded[..unpadded], &mut decoded[..len]), Ok(len), "unpadded decode fails for len={}", len);
+                assert_eq!(&decoded[..len], &input[..]);
+            }
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_match_plain_decode_errors() {
+    const INVALID: [u8; 6] = [b'*', b'.', 0x80, 0xff, 0, PAD];
+
+    for pair in PAIRS.iter() {
+        let input = random_input(96);
+        let mut encoded = vec![0u8; 128];
+        assert_eq!(based64::encode(pair.table, &input, &mut encoded), Some(128));
+
+        for (codec, mirror_codec) in pair.codecs() {
+            let mut decoded = [0u8; 96];
+            for idx in 0..encoded.len() {
+                for invalid in INVALID {
+                    let mut corrupted = encoded.clone();
+                    corrupted[idx] = invalid;
+                    let mirror_corrupted = pair.translate(&corrupted);
+
+                    let result = codec.decode_to(&corrupted, &mut decoded);
+                    let expected = mirror_codec.decode_to(&mirror_corrupted, &mut decoded);
+                    assert_eq!(result, expected, "error mismatch for idx={} invalid={}", idx, invalid);
+                    if invalid != PAD {
+                        assert_eq!(result.map(|_| ()), reference_decode(pair.table, &corrupted).map(|_| ()), "error differs from reference for idx={} invalid={}", idx, invalid);
+                    }
+                    match result {
+                        Err(DecodeError::InvalidByte { offset, byte }) => {
+                            assert_eq!(offset, idx);
+                            assert_eq!(byte, invalid);
+                        },
+                        Err(DecodeError::InvalidPadding | DecodeError::InvalidLength | DecodeError::TrailingBits) => assert_eq!(invalid, PAD),
+                        Ok(len) => assert_eq!(invalid, PAD, "decoded {} bytes", len),
+                        Err(error) => panic!("Unexpected error {:?}", error),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_match_reference_implementation() {
+    for table in [STANDARD_TABLE, URL_TABLE, &STANDARD_MIRROR, &URL_MIRROR] {
+        let codec = Codec::new(table);
+        for len in 0..256 {
+            let input = random_input(len);
+            let expected = reference_encode(table, &input);
+
+            let mut encoded = vec![0u8; expected.len()];
+            assert_eq!(codec.encode_to(&input, &mut encoded), Some(expected.len()));
+            assert_eq!(encoded, expected, "encode mismatch for len={}", len);
+
+            let mut decoded = vec![0u8; len];
+            assert_eq!(codec.decode_to(&expected, &mut decoded), Ok(len));
+            assert_eq!(reference_decode(table, &expected).as_ref(), Ok(&decoded), "decode differs from reference for len={}", len);
+            assert_eq!(decoded, input, "decode mismatch for len={}", len);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "alloc")]
+
+use based64::armor::{self, Armor, ArmorError};
+use based64::{DecodeError, STANDARD_CODEC};
+
+fn checksum_line(data: &[u8]) -> String {
+    let crc = armor::crc24(data).to_be_bytes();
+    format!("={}", STANDARD_CODEC.encode_into_string(&crc[1..]))
+}
+
+#[test]
+fn should_compute_crc24() {
+    assert_eq!(armor::crc24(b""), 0xb704ce);
+    assert_eq!(armor::crc24(b"123456789"), 0x21cf02);
+}
+
+#[test]
+fn should_encode_and_parse_armor() {
+    let data: Vec<u8> = (0..=255).collect();
+    let text = armor::encode(armor::MESSAGE, &data);
+    assert!(text.starts_with("-----BEGIN PGP MESSAGE-----\n\n"));
+    assert!(text.ends_with(&format!("\n{}\n-----END PGP MESSAGE-----\n", checksum_line(&data))));
+    assert!(text.lines().all(|line| line.len() <= 64));
+
+    let armor = armor::parse(text.as_bytes()).expect("to parse");
+    assert_eq!(armor, Armor::new(armor::MESSAGE, data));
+
+    let crlf = text.replace('\n', "\r\n");
+    assert_eq!(armor::parse(crlf.as_bytes()), Ok(armor));
+
+    let empty = armor::encode(armor::SIGNATURE, b"");
+    assert_eq!(empty, "-----BEGIN PGP SIGNATURE-----\n\n=twTO\n-----END PGP SIGNATURE-----\n");
+    assert_eq!(armor::parse(empty.as_bytes()), Ok(Armor::new(armor::SIGNATURE, Vec::new())));
+}
+
+#[test]
+fn should_parse_armor_headers() {
+    let input = format!("Text before armor
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+Version: GnuPG v2
+Comment: Key: with colon
+
+Zm9vYmFy
+{}
+-----END PGP PUBLIC KEY BLOCK-----
+Text after armor", checksum_line(b"foobar"));
+
+    let armor = armor::parse(input.as_bytes()).expect("to parse");
+    assert_eq!(armor.label, armor::PUBLIC_KEY_BLOCK);
+    assert_eq!(armor.headers, [
+        ("Version".to_owned(), "GnuPG v2".to_owned()),
+        ("Comment".to_owned(), "Key: with colon".to_owned()),
+    ]);
+    assert_eq!(armor.header("version"), Some("GnuPG v2"));
+    assert_eq!(armor.header("Charset"), None);
+    assert_eq!(armor.data, b"foobar");
+    assert_eq!(armor::parse(armor.encode().as_bytes()), Ok(armor));
+
+    let mut armor = Armor::new(armor::MESSAGE, b"foo".to_vec());
+    armor.headers.push(("Comment".to_owned(), String::new()));
+    armor.headers.push(("Hash".to_owned(), "SHA256".to_owned()));
+    let text = armor.encode();
+    assert!(text.contains("\nComment: \nHash: SHA256\n"));
+    assert_eq!(armor::parse(text.as_bytes()), Ok(armor));
+
+    let missing_empty_line = input.replace("colon\n\n", "colon\n");
+    assert_eq!(armor::parse(missing_empty_line.as_bytes()), Err(ArmorError::InvalidHeader { offset: 98 }));
+}
+
+#[test]
+fn should_parse_armor_without_checksum() {
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9vYmFy\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Ok(Armor::new(armor::MESSAGE, &b"foobar"[..])));
+}
+
+#[test]
+fn should_ignore_whitespace_in_armored_data() {
+    let input = "-----BEGIN PGP MESSAGE-----\n\n Zm9v\tYmFy \n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Ok(Armor::new(armor::MESSAGE, &b"foobar"[..])));
+}
+
+#[test]
+fn should_not_confuse_padding_with_checksum() {
+    let input = format!("-----BEGIN PGP MESSAGE-----\n\nZm9vYg\n==\n{}\n-----END PGP MESSAGE-----\n", checksum_line(b"foob"));
+    assert_eq!(armor::parse(input.as_bytes()), Ok(Armor::new(armor::MESSAGE, &b"foob"[..])));
+
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9vYg\n==\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Ok(Armor::new(armor::MESSAGE, &b"foob"[..])));
+}
+
+#[test]
+fn should_reject_invalid_checksum() {
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9vYmFy\n=AAAA\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::ChecksumMismatch {
+        expected: 0,
+        actual: armor::crc24(b"foobar"),
+    }));
+
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9vYmFy\n=AAA\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::InvalidChecksum { offset: 38 }));
+
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9vYmFy\n=AA!A\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::InvalidChecksum { offset: 38 }));
+
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9v\n=AAAA\nYmFy\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::InvalidChecksum { offset: 34 }));
+}
+
+#[test]
+fn should_reject_invalid_armor() {
+    assert_eq!(armor::parse(b"no armor"), Err(ArmorError::MissingBegin));
+
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9vYmFy\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::MissingEnd { label: armor::MESSAGE.to_owned() }));
+
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9vYmFy\n-----END PGP SIGNATURE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::LabelMismatch {
+        begin: armor::MESSAGE.to_owned(),
+        end: armor::SIGNATURE.to_owned(),
+    }));
+
+    let input = "-----BEGIN PGP MESSAGE----\n\nZm9vYmFy\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::InvalidBoundary { offset: 0 }));
+
+    let input = "-----BEGIN PGP MESSAGE-----\n\nZm9v*mFy\n-----END PGP MESSAGE-----\n";
+    assert_eq!(armor::parse(input.as_bytes()), Err(ArmorError::Decode(DecodeError::InvalidByte { offset: 33, byte: b'*' })));
+}
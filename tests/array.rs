@@ -0,0 +1,175 @@
+use based64::{Codec, LineEnding, LineWrap, Padding, STANDARD_CODEC, STANDARD_TABLE, URL_TABLE};
+
+use std::panic;
+
+const URL: Codec<'static> = Codec::new(URL_TABLE).with_padding(Padding::Forbidden);
+const STRICT: Codec<'static> = Codec::new(STANDARD_TABLE).with_strict(true);
+const REQUIRED: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Required);
+const WRAPPED: Codec<'static> = Codec::new(STANDARD_TABLE).with_line_wrap(Some(LineWrap::new(5, LineEnding::CrLf)));
+const WHITESPACE: Codec<'static> = Codec::new(STANDARD_TABLE).with_skip_whitespace(true).with_strict(true);
+
+const ENCODED: [u8; 8] = STANDARD_CODEC.encode_array(b"foobar");
+const DECODED: [u8; 6] = STANDARD_CODEC.decode_array(b"Zm9vYmFy");
+const KEY: [u8; 4] = URL.decode_array(b"-_-_-w");
+const WRAPPED_KEY: [u8; 10] = WRAPPED.encode_array(b"foob");
+
+fn random_input<const N: usize>() -> [u8; N] {
+    let mut input = [0u8; N];
+    getrandom::getrandom(&mut input).expect("Random should work");
+    input
+}
+
+///Decodes at runtime with codec, expecting the same outcome as `decode_array`.
+fn assert_decode_array<const N: usize, const M: usize>(codec: &Codec<'_>, encoded: &[u8; N]) {
+    let mut output = [0u8; 16];
+    let expected = codec.decode_to(encoded, &mut output).map(|len| output[..len].to_vec());
+    let actual = panic::catch_unwind(|| codec.decode_array::<N, M>(encoded).to_vec());
+    match expected {
+        Ok(expected) if expected.len() == M => assert_eq!(actual.ok(), Some(expected), "decode fails for {:?}", String::from_utf8_lossy(encoded)),
+        _ => assert!(actual.is_err(), "decode should fail for {:?}", String::from_utf8_lossy(encoded)),
+    }
+}
+
+///Decodes at runtime and with `decode_array`, expecting the same kind of error.
+fn assert_same_error<const N: usize>(codec: &Codec<'_>, encoded: &[u8; N]) {
+    let mut output = [0u8; 64];
+    let expected = codec.decode_to(encoded, &mut output);
+    //Output size never matches, so successful decoding panics too
+    let actual = panic::catch_unwind(|| codec.decode_array::<N, 64>(encoded)).expect_err("to panic");
+    let actual = match actual.downcast_ref::<String>() {
+        Some(message) => message.as_str(),
+        None => *actual.downcast_ref::<&str>().expect("to panic with message"),
+    };
+
+    match expected {
+        Ok(_) => assert_eq!(actual, "Output size must be equal to decoded length", "decode fails for {:?}", String::from_utf8_lossy(encoded)),
+        Err(error) => assert!(error.to_string().starts_with(actual), "{:?} is reported as {:?} for {:?}", error, actual, String::from_utf8_lossy(encoded)),
+    }
+}
+
+macro_rules! check_array_roundtrip {
+    ($codec:expr, $($len:literal),+) => {$({
+        const CODEC: Codec<'static> = $codec;
+        const ENCODED_LEN: usize = CODEC.encode_len($len);
+
+        let input = random_input::<$len>();
+        let encoded: [u8; ENCODED_LEN] = CODEC.encode_array(&input);
+        let mut expected = [0u8; ENCODED_LEN];
+        assert_eq!(CODEC.encode_to(&input, &mut expected), Some(ENCODED_LEN));
+        assert_eq!(encoded, expected);
+
+        let decoded: [u8; $len] = CODEC.decode_array(&encoded);
+        assert_eq!(decoded, input);
+
+        //Every single character replacement must behave as at runtime
+        for idx in 0..encoded.len() {
+            for ch in [b'=', b'*', b' ', b'\n', b'A', b'/', b'_'].iter() {
+                let mut encoded = encoded;
+                encoded[idx] = *ch;
+                assert_decode_array::<ENCODED_LEN, $len>(&CODEC, &encoded);
+            }
+        }
+    })+};
+}
+
+#[test]
+fn should_encode_and_decode_in_const_context() {
+    assert_eq!(&ENCODED, b"Zm9vYmFy");
+    assert_eq!(&DECODED, b"foobar");
+    assert_eq!(KEY, [0xfb, 0xff, 0xbf, 0xfb]);
+    assert_eq!(&WRAPPED_KEY, b"Zm9vY\r\ng==");
+
+    const EMPTY: [u8; 0] = STANDARD_CODEC.encode_array(b"");
+    const EMPTY_DECODED: [u8; 0] = STANDARD_CODEC.decode_array(b"");
+    assert_eq!(EMPTY, EMPTY_DECODED);
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_match_runtime_codec() {
+    panic::set_hook(Box::new(|_| {}));
+
+    check_array_roundtrip!(Codec::new(STANDARD_TABLE), 0, 1, 2, 3, 4, 5, 6);
+    check_array_roundtrip!(URL, 0, 1, 2, 3, 4, 5, 6);
+    check_array_roundtrip!(STRICT, 0, 1, 2, 3, 4, 5, 6);
+    check_array_roundtrip!(REQUIRED, 0, 1, 2, 3, 4, 5, 6);
+    check_array_roundtrip!(WRAPPED, 0, 1, 2, 3, 4, 5, 6, 7, 8);
+    check_array_roundtrip!(WHITESPACE, 0, 1, 2, 3, 4, 5, 6);
+
+    let _ = panic::take_hook();
+}
+
+#[test]
+fn should_report_same_errors_as_runtime() {
+    const CODECS: [Codec<'static>; 9] = [
+        Codec::new(STANDARD_TABLE),
+        STRICT,
+        REQUIRED,
+        REQUIRED.with_strict(true),
+        Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden),
+        Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden).with_strict(true),
+        WHITESPACE,
+        Codec::new(STANDARD_TABLE).with_skip_whitespace(true),
+        REQUIRED.with_skip_whitespace(true),
+    ];
+
+    for codec in CODECS.iter() {
+        assert_same_error(codec, b"");
+        assert_same_error(codec, b"QQ==");
+        assert_same_error(codec, b"QQ");
+        assert_same_error(codec, b"QQ=");
+        assert_same_error(codec, b"QQ===");
+        assert_same_error(codec, b"QQ==QQ==");
+        assert_same_error(codec, b"QQ==\nQQ==");
+        assert_same_error(codec, b"QQ== ");
+        assert_same_error(codec, b"Q===");
+        assert_same_error(codec, b"====");
+        assert_same_error(codec, b"=Zm9");
+        assert_same_error(codec, b"Zm=v");
+        assert_same_error(codec, b"Zm9v=");
+        assert_same_error(codec, b"Zm9vY");
+        assert_same_error(codec, b"Zm9vYg");
+        assert_same_error(codec, b"Zm9vYg=a");
+        assert_same_error(codec, b"Zm9vYmE");
+        assert_same_error(codec, b"Zm9vYmE=");
+        assert_same_error(codec, b"Zm9vYmE==");
+        assert_same_error(codec, b"Zm9vYmFy");
+        assert_same_error(codec, b"Zm9v Zm9v");
+        assert_same_error(codec, b"Zm9*");
+        assert_same_error(codec, b"Zm9vYh==");
+        assert_same_error(codec, b"Zh==");
+        assert_same_error(codec, b"Zh");
+    }
+}
+
+#[test]
+fn should_decode_whitespace_and_padding_variants() {
+    const WHITESPACE_DECODED: [u8; 4] = WHITESPACE.decode_array(b" Zm9v\r\nYg ==\n");
+    const UNPADDED: [u8; 4] = STANDARD_CODEC.decode_array(b"Zm9vYg");
+    assert_eq!(&WHITESPACE_DECODED, b"foob");
+    assert_eq!(&UNPADDED, b"foob");
+}
+
+#[test]
+#[should_panic(expected = "Output size must be equal to encoded length")]
+fn should_panic_on_wrong_encoded_size() {
+    let _: [u8; 4] = STANDARD_CODEC.encode_array(b"foob");
+}
+
+#[test]
+#[should_panic(expected = "Output size must be equal to decoded length")]
+fn should_panic_on_wrong_decoded_size() {
+    let _: [u8; 3] = STANDARD_CODEC.decode_array(b"Zm9vYg==");
+}
+
+#[test]
+#[should_panic(expected = "Invalid byte")]
+fn should_panic_on_invalid_input() {
+    let _: [u8; 3] = STANDARD_CODEC.decode_array(b"Zm9*");
+}
+
+#[test]
+#[should_panic(expected = "Non-zero trailing bits in last character")]
+fn should_panic_on_trailing_bits_in_strict_mode() {
+    let _: [u8; 1] = STRICT.decode_array(b"Zh==");
+}
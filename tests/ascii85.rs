@@ -0,0 +1,145 @@
+use based64::DecodeError;
+use based64::ascii85::{self, Codec, ADOBE_CODEC, BTOA_CODEC};
+
+const PLAIN: Codec = Codec::new();
+
+fn encode(codec: &Codec, input: &[u8]) -> String {
+    let mut encoded = vec![0u8; codec.encode_len(input.len())];
+    let len = codec.encode_to(input, &mut encoded).expect("to encode");
+    encoded.truncate(len);
+    String::from_utf8(encoded).expect("to be ASCII")
+}
+
+fn decode(codec: &Codec, input: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = vec![0u8; codec.decode_len(input.as_bytes())];
+    let len = codec.decode_to(input.as_bytes(), &mut decoded)?;
+    assert_eq!(len, decoded.len());
+    Ok(decoded)
+}
+
+#[test]
+fn should_encode_known_vectors() {
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b".", "/c"),
+        (b"Man ", "9jqo^"),
+        (b"sure.", "F*2M7/c"),
+        (b"Man is distinguished", "9jqo^BlbD-BleB1DJ+*+F(f,q"),
+        (b"\xff\xff\xff\xff", "s8W-!"),
+        (b"\0\0\0\0", "!!!!!"),
+    ];
+
+    for (input, expected) in VECTORS.iter() {
+        assert_eq!(encode(&PLAIN, input), *expected);
+        assert_eq!(decode(&PLAIN, expected).expect("to decode"), *input);
+        assert_eq!(ascii85::encode_len(input.len()), expected.len());
+        assert_eq!(ascii85::decode_len(expected.as_bytes()), input.len());
+        assert_eq!(encode(&ADOBE_CODEC.with_zero_compression(false), input), format!("<~{}~>", expected));
+    }
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_roundtrip_random_data() {
+    for len in 0..200 {
+        let mut input = vec![0u8; len];
+        getrandom::getrandom(&mut input).expect("Random should work");
+        //Make sure compressed groups occur
+        for chunk in input.chunks_mut(12).filter(|chunk| chunk.len() == 12) {
+            chunk[..4].copy_from_slice(&[0; 4]);
+            chunk[4..8].copy_from_slice(b"    ");
+        }
+
+        for codec in [&PLAIN, &ADOBE_CODEC, &BTOA_CODEC].iter() {
+            let encoded = encode(codec, &input);
+            assert_eq!(decode(codec, &encoded).expect("to decode"), input);
+            assert!(encoded.len() <= codec.encode_len(len));
+
+            let data = encoded.trim_start_matches("<~").trim_end_matches("~>");
+            let wrapped = data.as_bytes().chunks(7).map(|line| core::str::from_utf8(line).expect("to be ASCII")).collect::<Vec<_>>().join("\n ");
+            let wrapped = encoded.replace(data, &wrapped);
+            assert_eq!(decode(codec, &wrapped).expect("to decode"), input);
+        }
+    }
+}
+
+#[test]
+fn should_compress_groups() {
+    assert_eq!(encode(&ADOBE_CODEC, b"\0\0\0\0\0\0\0\0\0"), "<~zz!!~>");
+    assert_eq!(encode(&ADOBE_CODEC, b"    "), "<~+<VdL~>");
+    assert_eq!(encode(&BTOA_CODEC, b"    \0\0\0\0"), "yz");
+    assert_eq!(encode(&BTOA_CODEC, b"\0\0\0"), "!!!!");
+
+    assert_eq!(decode(&BTOA_CODEC, "y z").expect("to decode"), b"    \0\0\0\0");
+    assert_eq!(decode(&ADOBE_CODEC, "<~z~>").expect("to decode"), [0; 4]);
+    assert_eq!(decode(&ADOBE_CODEC, "<~y~>"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b'y',
+    }));
+    assert_eq!(decode(&PLAIN, "z"), Err(DecodeError::InvalidByte {
+        offset: 0,
+        byte: b'z',
+    }));
+    assert_eq!(decode(&BTOA_CODEC, "!!z"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b'z',
+    }));
+}
+
+#[test]
+fn should_validate_delimiters() {
+    assert_eq!(decode(&ADOBE_CODEC, " \n<~9jqo^ \n~>\n").expect("to decode"), b"Man ");
+    assert_eq!(decode(&ADOBE_CODEC, "<~~>").expect("to decode"), b"");
+    assert_eq!(decode(&ADOBE_CODEC, ""), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&ADOBE_CODEC, "<"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&ADOBE_CODEC, "<~9jqo^"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&ADOBE_CODEC, "<~9jqo^~"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&ADOBE_CODEC, "9jqo^"), Err(DecodeError::InvalidByte {
+        offset: 0,
+        byte: b'9',
+    }));
+    assert_eq!(decode(&ADOBE_CODEC, " <9jqo^~>"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b'9',
+    }));
+    assert_eq!(decode(&ADOBE_CODEC, "<~9jqo^~x"), Err(DecodeError::InvalidByte {
+        offset: 8,
+        byte: b'x',
+    }));
+    assert_eq!(decode(&ADOBE_CODEC, "<~9jqo^~>!!"), Err(DecodeError::InvalidByte {
+        offset: 9,
+        byte: b'!',
+    }));
+}
+
+#[test]
+fn should_report_invalid_input() {
+    assert_eq!(decode(&PLAIN, "9jqo^B"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&PLAIN, "9jq~^"), Err(DecodeError::InvalidByte {
+        offset: 3,
+        byte: b'~',
+    }));
+    assert_eq!(decode(&PLAIN, "s8W-\""), Err(DecodeError::InvalidByte {
+        offset: 4,
+        byte: b'"',
+    }));
+    assert_eq!(decode(&PLAIN, "uuuu"), Err(DecodeError::InvalidByte {
+        offset: 3,
+        byte: b'u',
+    }));
+
+    let mut output = [0u8; 3];
+    assert_eq!(PLAIN.decode_to(b"9jqo^", &mut output), Err(DecodeError::OutputTooSmall {
+        required: 4,
+    }));
+    assert_eq!(PLAIN.encode_to(b"Man", &mut output), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn should_encode_and_decode_vec() {
+    assert_eq!(ADOBE_CODEC.encode_into_string(b"Man is distinguished"), "<~9jqo^BlbD-BleB1DJ+*+F(f,q~>");
+    assert_eq!(BTOA_CODEC.encode_into_vec(b"    "), b"y");
+    assert_eq!(ADOBE_CODEC.decode_into_vec(b"<~9jqo^BlbD-\nBleB1DJ+*+F(f,q~>").expect("to decode"), b"Man is distinguished");
+    assert_eq!(PLAIN.decode_into_vec(b"9"), Err(DecodeError::InvalidLength));
+}
@@ -0,0 +1,127 @@
+use based64::DecodeError;
+use based64::base16::{self, Case, Codec, Separator, LOWER_CODEC, UPPER_CODEC};
+
+const COLON: Codec = Codec::new(Case::Upper).with_separator(Some(Separator::Colon));
+const SPACE: Codec = Codec::new(Case::Lower).with_separator(Some(Separator::Space));
+
+fn encode(codec: &Codec, input: &[u8]) -> String {
+    let mut encoded = vec![0u8; codec.encode_len(input.len())];
+    assert_eq!(codec.encode_to(input, &mut encoded), Some(encoded.len()));
+    String::from_utf8(encoded).expect("to be ASCII")
+}
+
+fn decode(codec: &Codec, input: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = vec![0u8; codec.decode_len(input.as_bytes())];
+    let len = codec.decode_to(input.as_bytes(), &mut decoded)?;
+    assert_eq!(len, decoded.len());
+    Ok(decoded)
+}
+
+#[test]
+fn should_encode_rfc4648_vectors() {
+    const VECTORS: &[(&str, &str)] = &[
+        ("", ""),
+        ("f", "66"),
+        ("fo", "666F"),
+        ("foo", "666F6F"),
+        ("foob", "666F6F62"),
+        ("fooba", "666F6F6261"),
+        ("foobar", "666F6F626172"),
+    ];
+
+    for (input, expected) in VECTORS.iter() {
+        assert_eq!(encode(&UPPER_CODEC, input.as_bytes()), *expected);
+        assert_eq!(encode(&LOWER_CODEC, input.as_bytes()), expected.to_ascii_lowercase());
+        assert_eq!(decode(&UPPER_CODEC, expected).expect("to decode"), input.as_bytes());
+        assert_eq!(decode(&UPPER_CODEC, &expected.to_ascii_lowercase()).expect("to decode"), input.as_bytes());
+        assert_eq!(base16::decode_len(expected.as_bytes()), input.len());
+    }
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_roundtrip_random_data() {
+    for len in (0..200).chain([255, 256, 257, 1000, 4096].iter().copied()) {
+        let mut input = vec![0u8; len];
+        getrandom::getrandom(&mut input).expect("Random should work");
+
+        let expected = input.iter().map(|byte| format!("{:02X}", byte)).collect::<String>();
+        assert_eq!(encode(&UPPER_CODEC, &input), expected);
+        assert_eq!(encode(&LOWER_CODEC, &input), expected.to_ascii_lowercase());
+        assert_eq!(base16::encode_len(len), expected.len());
+        assert_eq!(decode(&LOWER_CODEC, &expected).expect("to decode"), input);
+
+        let expected = input.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(":");
+        assert_eq!(encode(&COLON, &input), expected);
+        assert_eq!(decode(&COLON, &expected).expect("to decode"), input);
+    }
+}
+
+#[test]
+fn should_report_invalid_byte_offset() {
+    let mut input = b"00112233445566778899aabbccddeeff".repeat(4);
+    for offset in [0, 15, 16, 31, 32, 63, 64, 127].iter().copied() {
+        let original = input[offset];
+        input[offset] = b'g';
+        let input_str = core::str::from_utf8(&input).expect("to be ASCII");
+        assert_eq!(decode(&UPPER_CODEC, input_str), Err(DecodeError::InvalidByte {
+            offset,
+            byte: b'g',
+        }));
+        input[offset] = original;
+    }
+
+    assert_eq!(decode(&UPPER_CODEC, "0"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&UPPER_CODEC, "001"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&UPPER_CODEC, "00\u{e9}"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: 0xc3,
+    }));
+
+    let mut output = [0u8; 1];
+    assert_eq!(UPPER_CODEC.decode_to(b"0011", &mut output), Err(DecodeError::OutputTooSmall {
+        required: 2,
+    }));
+    assert_eq!(UPPER_CODEC.encode_to(b"f", &mut output), None);
+}
+
+#[test]
+fn should_use_separator() {
+    assert_eq!(encode(&COLON, b""), "");
+    assert_eq!(encode(&COLON, b"\x01"), "01");
+    assert_eq!(encode(&COLON, &[0xab, 0xcd, 0xef]), "AB:CD:EF");
+    assert_eq!(encode(&SPACE, &[0xab, 0xcd, 0xef]), "ab cd ef");
+    assert_eq!(COLON.encode_len(3), 8);
+    assert_eq!(COLON.encode_len(0), 0);
+
+    assert_eq!(decode(&COLON, "").expect("to decode"), b"");
+    assert_eq!(decode(&COLON, "ab:CD:eF").expect("to decode"), [0xab, 0xcd, 0xef]);
+    assert_eq!(decode(&SPACE, "AB CD EF").expect("to decode"), [0xab, 0xcd, 0xef]);
+    assert_eq!(decode(&COLON, "AB:CD:"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&COLON, "AB:C"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&COLON, "ABCD"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b'C',
+    }));
+    assert_eq!(decode(&COLON, "AB CD"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b' ',
+    }));
+    assert_eq!(decode(&UPPER_CODEC, "AB:CD"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b':',
+    }));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn should_encode_and_decode_vec() {
+    assert_eq!(LOWER_CODEC.encode_into_string(b"foobar"), "666f6f626172");
+    assert_eq!(COLON.encode_into_vec(b"foo"), b"66:6F:6F");
+    assert_eq!(UPPER_CODEC.decode_into_vec(b"666f6F626172").expect("to decode"), b"foobar");
+    assert_eq!(SPACE.decode_into_vec(b"66 6f 6f").expect("to decode"), b"foo");
+    assert_eq!(UPPER_CODEC.decode_into_vec(b"66x"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b'x',
+    }));
+}
@@ -0,0 +1,172 @@
+use based64::{DecodeError, Padding};
+use based64::base32::{self, Codec, CROCKFORD_CODEC, CROCKFORD_TABLE, HEX_CODEC, STANDARD_CODEC, STANDARD_TABLE, Z_CODEC};
+
+const UNPADDED: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden);
+const REQUIRED: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Required);
+const STRICT: Codec<'static> = Codec::new(STANDARD_TABLE).with_strict(true);
+const CHECKED: Codec<'static> = CROCKFORD_CODEC.with_check_symbol(true);
+
+fn encode(codec: &Codec<'_>, input: &[u8]) -> String {
+    let mut encoded = vec![0u8; codec.encode_len(input.len())];
+    assert_eq!(codec.encode_to(input, &mut encoded), Some(encoded.len()));
+    String::from_utf8(encoded).expect("to be ASCII")
+}
+
+fn decode(codec: &Codec<'_>, input: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = vec![0u8; codec.decode_len(input.as_bytes())];
+    let len = codec.decode_to(input.as_bytes(), &mut decoded)?;
+    assert_eq!(len, decoded.len());
+    Ok(decoded)
+}
+
+#[test]
+fn should_encode_rfc4648_vectors() {
+    const VECTORS: &[(&str, &str, &str)] = &[
+        ("", "", ""),
+        ("f", "MY======", "CO======"),
+        ("fo", "MZXQ====", "CPNG===="),
+        ("foo", "MZXW6===", "CPNMU==="),
+        ("foob", "MZXW6YQ=", "CPNMUOG="),
+        ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+        ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ];
+
+    for (input, standard, hex) in VECTORS.iter() {
+        assert_eq!(encode(&STANDARD_CODEC, input.as_bytes()), *standard);
+        assert_eq!(encode(&HEX_CODEC, input.as_bytes()), *hex);
+        assert_eq!(encode(&UNPADDED, input.as_bytes()), standard.trim_end_matches('='));
+        assert_eq!(decode(&STANDARD_CODEC, standard).expect("to decode"), input.as_bytes());
+        assert_eq!(decode(&HEX_CODEC, hex).expect("to decode"), input.as_bytes());
+        assert_eq!(decode(&STANDARD_CODEC, standard.trim_end_matches('=')).expect("to decode"), input.as_bytes());
+        assert_eq!(base32::decode_len(standard.as_bytes()), input.len());
+    }
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_roundtrip_random_data() {
+    for len in 0..200 {
+        let mut input = vec![0u8; len];
+        getrandom::getrandom(&mut input).expect("Random should work");
+
+        for codec in [&STANDARD_CODEC, &HEX_CODEC, &Z_CODEC, &CROCKFORD_CODEC, &UNPADDED, &REQUIRED, &STRICT, &CHECKED].iter() {
+            let encoded = encode(codec, &input);
+            assert_eq!(decode(codec, &encoded).expect("to decode"), input);
+        }
+
+        assert_eq!(base32::encode_len(len), encode(&STANDARD_CODEC, &input).len());
+        assert_eq!(base32::encode_len_unpadded(len), encode(&UNPADDED, &input).len());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn should_encode_and_decode_vec() {
+    assert_eq!(STANDARD_CODEC.encode_into_string(b"foobar"), "MZXW6YTBOI======");
+    assert_eq!(Z_CODEC.encode_into_vec(b"foobar"), b"c3zs6aubqe");
+    assert_eq!(CHECKED.encode_into_string(b"foobar"), "CSQPYRK1E86");
+    assert_eq!(STANDARD_CODEC.decode_into_vec(b"MZXW6YTBOI").expect("to decode"), b"foobar");
+    assert_eq!(CROCKFORD_CODEC.decode_into_vec(b"csqp-yrk1-e8").expect("to decode"), b"foobar");
+}
+
+#[test]
+fn should_decode_crockford_aliases() {
+    assert_eq!(encode(&CROCKFORD_CODEC, &[0x08, 0x42, 0x10, 0x84, 0x21]), "11111111");
+    assert_eq!(decode(&CROCKFORD_CODEC, "IiLl-1111").expect("to decode"), [0x08, 0x42, 0x10, 0x84, 0x21]);
+    assert_eq!(decode(&CROCKFORD_CODEC, "oO000000").expect("to decode"), [0; 5]);
+    assert_eq!(decode(&CROCKFORD_CODEC, "-").expect("to decode"), b"");
+    assert_eq!(decode(&CROCKFORD_CODEC, "11U"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b'U',
+    }));
+
+    //Aliases and hyphens are not accepted by other codecs
+    let plain = Codec::new(CROCKFORD_TABLE);
+    assert_eq!(decode(&plain, "1111111I"), Err(DecodeError::InvalidByte {
+        offset: 7,
+        byte: b'I',
+    }));
+    assert_eq!(decode(&plain, "1111-1111"), Err(DecodeError::InvalidByte {
+        offset: 4,
+        byte: b'-',
+    }));
+}
+
+#[test]
+fn should_verify_check_symbol() {
+    assert_eq!(encode(&CHECKED, b""), "0");
+    assert_eq!(encode(&CHECKED, &[32]), "40*");
+    assert_eq!(encode(&CHECKED, &[36]), "4GU");
+    assert_eq!(encode(&CHECKED, &[35]), "4C=");
+    assert_eq!(encode(&CHECKED, &[1]), "041");
+
+    assert_eq!(decode(&CHECKED, "0").expect("to decode"), b"");
+    assert_eq!(decode(&CHECKED, "40*").expect("to decode"), [32]);
+    assert_eq!(decode(&CHECKED, "4gu").expect("to decode"), [36]);
+    assert_eq!(decode(&CHECKED, "04-1-").expect("to decode"), [1]);
+    assert_eq!(decode(&CHECKED, "04L").expect("to decode"), [1]);
+    assert_eq!(decode(&CHECKED, "042"), Err(DecodeError::InvalidCheckSymbol));
+    assert_eq!(decode(&CHECKED, "04#"), Err(DecodeError::InvalidByte {
+        offset: 2,
+        byte: b'#',
+    }));
+    assert_eq!(decode(&CHECKED, ""), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&CHECKED, "--"), Err(DecodeError::InvalidLength));
+}
+
+#[test]
+fn should_validate_padding() {
+    assert_eq!(decode(&STANDARD_CODEC, "MZXW6==="), Ok(b"foo".to_vec()));
+    assert_eq!(decode(&STANDARD_CODEC, "MZXW6=="), Err(DecodeError::InvalidPadding));
+    assert_eq!(decode(&STANDARD_CODEC, "MZXW6===="), Err(DecodeError::InvalidPadding));
+    assert_eq!(decode(&STANDARD_CODEC, "MZXW6YTB========"), Err(DecodeError::InvalidPadding));
+    assert_eq!(decode(&STANDARD_CODEC, "MZ=XW6=="), Err(DecodeError::InvalidPadding));
+    assert_eq!(decode(&REQUIRED, "MZXW6"), Err(DecodeError::InvalidPadding));
+    assert_eq!(decode(&REQUIRED, "MZXW6YTB"), Ok(b"fooba".to_vec()));
+    assert_eq!(decode(&UNPADDED, "MZXW6==="), Err(DecodeError::InvalidPadding));
+    assert_eq!(decode(&UNPADDED, "MZXW6"), Ok(b"foo".to_vec()));
+}
+
+#[test]
+fn should_report_invalid_input() {
+    assert_eq!(decode(&STANDARD_CODEC, "MZX"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&STANDARD_CODEC, "M"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&STANDARD_CODEC, "MZXW6Y"), Err(DecodeError::InvalidLength));
+    assert_eq!(decode(&STANDARD_CODEC, "MZXW1YTB"), Err(DecodeError::InvalidByte {
+        offset: 4,
+        byte: b'1',
+    }));
+    assert_eq!(decode(&STANDARD_CODEC, "mzxw6ytb"), Err(DecodeError::InvalidByte {
+        offset: 0,
+        byte: b'm',
+    }));
+    assert_eq!(decode(&STANDARD_CODEC.with_case_insensitive(true), "mzXw6ytb"), Ok(b"fooba".to_vec()));
+
+    assert_eq!(decode(&STANDARD_CODEC, "MZ"), Ok(b"f".to_vec()));
+    assert_eq!(decode(&STANDARD_CODEC, "MZ======"), Ok(b"f".to_vec()));
+    assert_eq!(decode(&STRICT, "MZ"), Err(DecodeError::TrailingBits));
+    assert_eq!(decode(&STRICT, "MY"), Ok(b"f".to_vec()));
+
+    let mut output = [0u8; 2];
+    assert_eq!(STANDARD_CODEC.decode_to(b"MZXW6===", &mut output), Err(DecodeError::OutputTooSmall {
+        required: 3,
+    }));
+    assert_eq!(STANDARD_CODEC.encode_to(b"foo", &mut output), None);
+}
+
+#[test]
+fn should_use_z_base32() {
+    assert_eq!(encode(&Z_CODEC, b""), "");
+    assert_eq!(encode(&Z_CODEC, b"f"), "ca");
+    assert_eq!(encode(&Z_CODEC, b"foobar"), "c3zs6aubqe");
+    assert_eq!(decode(&Z_CODEC, "c3zs6aubqe").expect("to decode"), b"foobar");
+    assert_eq!(decode(&Z_CODEC, "ca======"), Err(DecodeError::InvalidPadding));
+}
+
+#[test]
+fn should_validate_custom_table() {
+    assert!(base32::validate_character_table(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567").is_ok());
+    assert!(base32::validate_character_table(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ23456A").is_err());
+    assert!(base32::validate_character_table(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ23456=").is_err());
+    assert!(Codec::try_new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ23456\n").is_err());
+}
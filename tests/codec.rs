@@ -0,0 +1,40 @@
+use based64::{Codec, TableError, STANDARD_TABLE, URL_TABLE, validate_character_table};
+
+const fn replace(mut table: [u8; 64], idx: usize, byte: u8) -> [u8; 64] {
+    table[idx] = byte;
+    table
+}
+
+#[test]
+fn should_accept_valid_tables() {
+    assert_eq!(validate_character_table(STANDARD_TABLE), Ok(()));
+    assert_eq!(validate_character_table(URL_TABLE), Ok(()));
+    assert!(Codec::try_new(STANDARD_TABLE).is_ok());
+    assert!(Codec::try_new(URL_TABLE).is_ok());
+}
+
+#[test]
+fn should_reject_invalid_tables() {
+    static NON_ASCII: [u8; 64] = replace(*STANDARD_TABLE, 10, 0xc3);
+    static CONTROL: [u8; 64] = replace(*STANDARD_TABLE, 11, b'\n');
+    static SPACE: [u8; 64] = replace(*STANDARD_TABLE, 12, b' ');
+    static PAD: [u8; 64] = replace(*STANDARD_TABLE, 63, based64::PAD);
+    static DUPLICATE: [u8; 64] = replace(*STANDARD_TABLE, 40, b'C');
+
+    let cases = [
+        (&NON_ASCII, TableError::NonAscii { index: 10, byte: 0xc3 }),
+        (&CONTROL, TableError::NonPrintable { index: 11, byte: b'\n' }),
+        (&SPACE, TableError::NonPrintable { index: 12, byte: b' ' }),
+        (&PAD, TableError::PadCollision { index: 63 }),
+        (&DUPLICATE, TableError::DuplicateSymbol { first: 2, second: 40, byte: b'C' }),
+    ];
+
+    for (table, expected) in cases {
+        assert_eq!(validate_character_table(table), Err(expected));
+        assert_eq!(Codec::try_new(table).err(), Some(expected));
+        let result = std::panic::catch_unwind(|| Codec::new(table));
+        assert!(result.is_err(), "Codec::new() should panic on {:?}", expected);
+    }
+
+    assert_eq!(TableError::DuplicateSymbol { first: 2, second: 40, byte: b'C' }.to_string(), "Character table contains duplicate character: 0x43 at indices 2 and 40");
+}
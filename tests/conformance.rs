@@ -0,0 +1,88 @@
+//! RFC 4648 conformance vectors
+use based64::{STANDARD_TABLE, URL_TABLE, STANDARD_CODEC, URL_CODEC, Codec};
+
+///RFC 4648 section 10
+const RFC4648_VECTORS: [(&[u8], &str); 7] = [
+    (b"", ""),
+    (b"f", "Zg=="),
+    (b"fo", "Zm8="),
+    (b"foo", "Zm9v"),
+    (b"foob", "Zm9vYg=="),
+    (b"fooba", "Zm9vYmE="),
+    (b"foobar", "Zm9vYmFy"),
+];
+
+///RFC 4648 section 9 illustrations
+const RFC4648_STANDARD_VECTORS: [(&[u8], &str); 3] = [
+    (&[0x14, 0xfb, 0x9c, 0x03, 0xd9, 0x7e], "FPucA9l+"),
+    (&[0x14, 0xfb, 0x9c, 0x03, 0xd9], "FPucA9k="),
+    (&[0x14, 0xfb, 0x9c, 0x03], "FPucAw=="),
+];
+
+///Published base64url vectors (RFC 4648 section 9 with URL alphabet, RFC 7515 appendices A.1 and C)
+const URL_VECTORS: [(&[u8], &str); 4] = [
+    (&[0x14, 0xfb, 0x9c, 0x03, 0xd9, 0x7e], "FPucA9l-"),
+    (&[3, 236, 255, 224, 193], "A-z_4ME="),
+    (b"{\"typ\":\"JWT\",\r\n \"alg\":\"HS256\"}", "eyJ0eXAiOiJKV1QiLA0KICJhbGciOiJIUzI1NiJ9"),
+    (&[0xfb, 0xff, 0xbf], "-_-_"),
+];
+
+///Decoded value of whole alphabet, i.e. its encoding is exactly the character table.
+const ALPHABET_BYTES: [u8; 48] = [
+    0, 16, 131, 16, 81, 135, 32, 146, 139, 48, 211, 143, 65, 20, 147, 81, 85, 151, 97, 150, 155, 113, 215, 159,
+    130, 24, 163, 146, 89, 167, 162, 154, 171, 178, 219, 175, 195, 28, 179, 211, 93, 183, 227, 158, 187, 243, 223, 191
+];
+
+fn verify_vector(table: &'static [u8; 64], codec: &Codec<'_>, input: &[u8], expected: &str) {
+    let mut buffer = [0u8; 128];
+    let mut decoded = [0u8; 128];
+
+    let len = based64::encode(table, input, &mut buffer).expect("to encode");
+    assert_eq!(core::str::from_utf8(&buffer[..len]).unwrap(), expected);
+    let len = codec.encode_to(input, &mut buffer).expect("to encode");
+    assert_eq!(core::str::from_utf8(&buffer[..len]).unwrap(), expected);
+
+    let len = based64::decode(table, expected.as_bytes(), &mut decoded).expect("to decode");
+    assert_eq!(&decoded[..len], input);
+    let len = codec.decode_to(expected.as_bytes(), &mut decoded).expect("to decode");
+    assert_eq!(&decoded[..len], input);
+    let len = codec.with_strict(true).decode_to(expected.as_bytes(), &mut decoded).expect("to decode strictly");
+    assert_eq!(&decoded[..len], input);
+
+    #[cfg(feature = "alloc")]
+    {
+        assert_eq!(based64::vec::encode(table, input), expected.as_bytes());
+        assert_eq!(based64::string::encode(table, input), expected);
+        assert_eq!(codec.encode_into_vec(input), expected.as_bytes());
+        assert_eq!(codec.encode_into_string(input), expected);
+        assert_eq!(based64::vec::decode(table, expected.as_bytes()).expect("to decode"), input);
+        assert_eq!(codec.decode_into_vec(expected.as_bytes()).expect("to decode"), input);
+    }
+}
+
+#[test]
+fn should_conform_rfc4648_test_vectors() {
+    for (input, expected) in RFC4648_VECTORS.iter().chain(RFC4648_STANDARD_VECTORS.iter()) {
+        verify_vector(STANDARD_TABLE, &STANDARD_CODEC, input, expected);
+    }
+
+    //Section 10 vectors do not contain characters that differ between alphabets
+    for (input, expected) in RFC4648_VECTORS {
+        verify_vector(URL_TABLE, &URL_CODEC, input, expected);
+    }
+}
+
+#[test]
+fn should_conform_base64url_test_vectors() {
+    for (input, expected) in URL_VECTORS {
+        verify_vector(URL_TABLE, &URL_CODEC, input, expected);
+    }
+}
+
+#[test]
+fn should_encode_whole_alphabet() {
+    verify_vector(STANDARD_TABLE, &STANDARD_CODEC, &ALPHABET_BYTES, core::str::from_utf8(STANDARD_TABLE).unwrap());
+    verify_vector(URL_TABLE, &URL_CODEC, &ALPHABET_BYTES, core::str::from_utf8(URL_TABLE).unwrap());
+    assert!(core::ptr::eq(Codec::standard(), &STANDARD_CODEC));
+    assert!(core::ptr::eq(Codec::url_usafe(), &URL_CODEC));
+}
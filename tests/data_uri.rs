@@ -0,0 +1,67 @@
+#![cfg(feature = "alloc")]
+
+use based64::data_uri::{self, DataUri, DataUriError};
+use based64::DecodeError;
+
+#[test]
+fn should_encode_and_parse_base64_uri() {
+    let uri = data_uri::encode("image/png", b"\x89PNG\r\n");
+    assert_eq!(uri, "data:image/png;base64,iVBORw0K");
+    assert_eq!(data_uri::parse(&uri), Ok(DataUri::new("image/png", &b"\x89PNG\r\n"[..])));
+
+    let mut uri = DataUri::new("text/html", &b"<p>foobar</p>"[..]);
+    uri.parameters.push(("charset".to_owned(), "utf-8".to_owned()));
+    let text = uri.encode();
+    assert_eq!(text, "data:text/html;charset=utf-8;base64,PHA+Zm9vYmFyPC9wPg==");
+    let parsed = data_uri::parse(&text).expect("to parse");
+    assert_eq!(parsed.parameter("Charset"), Some("utf-8"));
+    assert_eq!(parsed.parameter("name"), None);
+    assert_eq!(parsed, uri);
+
+    assert_eq!(data_uri::encode("application/octet-stream", b""), "data:application/octet-stream;base64,");
+}
+
+#[test]
+fn should_parse_lenient_base64_data() {
+    let expected = Ok(DataUri::new("text/html", &b"<p>foobar</p>"[..]));
+    assert_eq!(data_uri::parse("DATA:text/html;BASE64,PHA+Zm9vYmFyPC9wPg"), expected);
+    assert_eq!(data_uri::parse("data:text/html;base64,PHA%2BZm9v%0AYmFyPC9wPg%3D%3D"), expected);
+    assert_eq!(data_uri::parse("data:text/html;base64,PHA+Zm9v\nYmFy PC9wPg=="), expected);
+}
+
+#[test]
+fn should_parse_percent_encoded_uri() {
+    let uri = data_uri::parse("data:,A%20brief%20note").expect("to parse");
+    assert_eq!(uri.media_type, data_uri::DEFAULT_MEDIA_TYPE);
+    assert_eq!(uri.parameter("charset"), Some("US-ASCII"));
+    assert_eq!(uri.data, b"A brief note");
+
+    let uri = data_uri::parse("data:;charset=iso-8859-7,%be%d3%be").expect("to parse");
+    assert_eq!(uri.media_type, data_uri::DEFAULT_MEDIA_TYPE);
+    assert_eq!(uri.parameters, [("charset".to_owned(), "iso-8859-7".to_owned())]);
+    assert_eq!(uri.data, b"\xbe\xd3\xbe");
+
+    let uri = data_uri::parse("data:text/plain,ünïcode").expect("to parse");
+    assert!(uri.parameters.is_empty());
+    assert_eq!(uri.data, "ünïcode".as_bytes());
+}
+
+#[test]
+fn should_reject_malformed_uri() {
+    assert_eq!(data_uri::parse("image/png;base64,AAAA"), Err(DataUriError::MissingScheme));
+    assert_eq!(data_uri::parse("data:image/png;base64"), Err(DataUriError::MissingComma));
+    assert_eq!(data_uri::parse("data:image,AAAA"), Err(DataUriError::InvalidMediaType));
+    assert_eq!(data_uri::parse("data:image/p(n)g,AAAA"), Err(DataUriError::InvalidMediaType));
+    assert_eq!(data_uri::parse("data:image/png;charset,AAAA"), Err(DataUriError::InvalidParameter { offset: 15 }));
+    assert_eq!(data_uri::parse("data:image/png;;base64,AAAA"), Err(DataUriError::InvalidParameter { offset: 15 }));
+    assert_eq!(data_uri::parse("data:image/png;base64;a=b,AAAA"), Err(DataUriError::InvalidParameter { offset: 22 }));
+    assert_eq!(data_uri::parse("data:,100%"), Err(DataUriError::InvalidPercentEncoding { offset: 9 }));
+    assert_eq!(data_uri::parse("data:,%zz"), Err(DataUriError::InvalidPercentEncoding { offset: 6 }));
+}
+
+#[test]
+fn should_report_decode_error_offset_within_uri() {
+    assert_eq!(data_uri::parse("data:image/png;base64,AA*A"), Err(DataUriError::Decode(DecodeError::InvalidByte { offset: 24, byte: b'*' })));
+    assert_eq!(data_uri::parse("data:image/png;base64,A%41*A"), Err(DataUriError::Decode(DecodeError::InvalidByte { offset: 26, byte: b'*' })));
+    assert_eq!(data_uri::parse("data:image/png;base64,A%41%2AA"), Err(DataUriError::Decode(DecodeError::InvalidByte { offset: 26, byte: b'*' })));
+}
@@ -1,4 +1,4 @@
-use based64::{STANDARD_TABLE, URL_TABLE, PAD};
+use based64::{STANDARD_TABLE, URL_TABLE, PAD, DecodeError};
 use based64::decode;
 
 #[test]
@@ -10,28 +10,61 @@ fn handling_pad_middle() {
     const EXPECTED: &str = "Hello World";
 
     let mut buffer = [0u8; 64];
-    assert_eq!(decode(STANDARD_TABLE, INPUT.as_bytes(), &mut buffer), Some(EXPECTED.len()));
+    assert_eq!(decode(STANDARD_TABLE, INPUT.as_bytes(), &mut buffer), Ok(EXPECTED.len()));
     assert_eq!(&buffer[..EXPECTED.len()], EXPECTED.as_bytes());
     buffer.fill(0);
-    assert_eq!(based64::STANDARD_CODEC.decode_to(INPUT.as_bytes(), &mut buffer), Some(EXPECTED.len()));
+    assert_eq!(based64::STANDARD_CODEC.decode_to(INPUT.as_bytes(), &mut buffer), Ok(EXPECTED.len()));
     assert_eq!(&buffer[..EXPECTED.len()], EXPECTED.as_bytes());
     buffer.fill(0);
-    assert_eq!(decode(URL_TABLE, INPUT.as_bytes(), &mut buffer), Some(EXPECTED.len()));
+    assert_eq!(decode(URL_TABLE, INPUT.as_bytes(), &mut buffer), Ok(EXPECTED.len()));
     assert_eq!(&buffer[..EXPECTED.len()], EXPECTED.as_bytes());
     buffer.fill(0);
-    assert_eq!(based64::URL_CODEC.decode_to(INPUT.as_bytes(), &mut buffer), Some(EXPECTED.len()));
+    assert_eq!(based64::URL_CODEC.decode_to(INPUT.as_bytes(), &mut buffer), Ok(EXPECTED.len()));
     assert_eq!(&buffer[..EXPECTED.len()], EXPECTED.as_bytes());
 }
 
 #[test]
 fn should_fail_on_invalid_char() {
     const INPUT: [u8; 4] = [b'n', 0xff, b'A', PAD];
+    const INVALID: DecodeError = DecodeError::InvalidByte { offset: 1, byte: 0xff };
 
     let mut buffer = [0u8; 64];
-    assert_eq!(decode(STANDARD_TABLE, &INPUT, &mut buffer), None);
-    assert_eq!(based64::STANDARD_CODEC.decode_to(&INPUT, &mut buffer), None);
-    assert_eq!(decode(URL_TABLE, &INPUT, &mut buffer), None);
-    assert_eq!(based64::URL_CODEC.decode_to(&INPUT, &mut buffer), None);
+    assert_eq!(decode(STANDARD_TABLE, &INPUT, &mut buffer), Err(INVALID));
+    assert_eq!(based64::STANDARD_CODEC.decode_to(&INPUT, &mut buffer), Err(INVALID));
+    assert_eq!(decode(URL_TABLE, &INPUT, &mut buffer), Err(INVALID));
+    assert_eq!(based64::URL_CODEC.decode_to(&INPUT, &mut buffer), Err(INVALID));
+}
+
+#[test]
+fn should_report_invalid_byte_offset() {
+    const INPUT: &str = "SGVsbG8gV29y*GQ=";
+
+    let mut buffer = [0u8; 64];
+    let expected = DecodeError::InvalidByte { offset: 12, byte: b'*' };
+    assert_eq!(decode(STANDARD_TABLE, INPUT.as_bytes(), &mut buffer), Err(expected));
+    assert_eq!(based64::STANDARD_CODEC.decode_to(INPUT.as_bytes(), &mut buffer), Err(expected));
+    #[cfg(feature = "alloc")]
+    {
+        assert_eq!(based64::vec::decode(STANDARD_TABLE, INPUT.as_bytes()), Err(expected));
+        assert_eq!(based64::STANDARD_CODEC.decode_into_vec(INPUT.as_bytes()), Err(expected));
+        assert_eq!(expected.to_string(), "Invalid byte 0x2a at offset 12");
+    }
+}
+
+#[test]
+fn should_report_invalid_byte_in_short_input() {
+    let expected = DecodeError::InvalidByte { offset: 0, byte: b'*' };
+
+    for input in [&b"*"[..], b"*==="] {
+        let mut buffer = [0u8; 4];
+        assert_eq!(decode(STANDARD_TABLE, input, &mut buffer), Err(expected));
+        assert_eq!(decode(STANDARD_TABLE, input, &mut []), Err(expected));
+        assert_eq!(based64::STANDARD_CODEC.decode_to(input, &mut buffer), Err(expected));
+        #[cfg(feature = "alloc")]
+        assert_eq!(based64::vec::decode(STANDARD_TABLE, input), Err(expected));
+    }
+
+    assert_eq!(decode(STANDARD_TABLE, b"", &mut []), Ok(0));
 }
 
 #[test]
@@ -64,10 +97,10 @@ fn should_handle_decode_padding_only() {
     let mut buffer = [0u8; 90];
     for idx in 1..buffer.len() {
         let input = "=".repeat(idx);
-        assert_eq!(decode(STANDARD_TABLE, input.as_bytes(), &mut buffer), Some(0));
-        assert_eq!(based64::STANDARD_CODEC.decode_to(input.as_bytes(), &mut buffer), Some(0));
-        assert_eq!(decode(URL_TABLE, input.as_bytes(), &mut buffer), Some(0));
-        assert_eq!(based64::URL_CODEC.decode_to(input.as_bytes(), &mut buffer), Some(0));
+        assert_eq!(decode(STANDARD_TABLE, input.as_bytes(), &mut buffer), Ok(0));
+        assert_eq!(based64::STANDARD_CODEC.decode_to(input.as_bytes(), &mut buffer), Ok(0));
+        assert_eq!(decode(URL_TABLE, input.as_bytes(), &mut buffer), Ok(0));
+        assert_eq!(based64::URL_CODEC.decode_to(input.as_bytes(), &mut buffer), Ok(0));
     }
 }
 
@@ -97,6 +130,7 @@ fn should_decode() {
 
 #[cfg_attr(miri, ignore)] // too slow
 #[cfg(feature = "alloc")]
+#[allow(clippy::char_lit_as_u8)]
 #[test]
 fn should_decode_big_unpadded() {
     fn generate_b64_data(size: usize) -> String {
@@ -119,3 +153,84 @@ fn should_decode_big_unpadded() {
         based64::STANDARD_CODEC.decode_into_vec(input.as_bytes()).unwrap();
     }
 }
+
+#[test]
+fn should_decode_strict_canonical_only() {
+    const STRICT: based64::Codec<'static> = based64::Codec::new(STANDARD_TABLE).with_strict(true);
+    const VALID: [(&str, &str); 7] = [
+        ("", ""),
+        ("Zg==", "f"),
+        ("Zm8=", "fo"),
+        ("Zm9v", "foo"),
+        ("Zm9vYg==", "foob"),
+        ("Zm9vYmE=", "fooba"),
+        ("Zm9vYmFy", "foobar"),
+    ];
+    const INVALID: [(&str, DecodeError); 13] = [
+        ("QR==", DecodeError::TrailingBits),
+        ("QUF=", DecodeError::TrailingBits),
+        ("Zm9vQUF=", DecodeError::TrailingBits),
+        ("SGVsbG8gV29ybGQ=SGVsbG8gV29ybGQ=", DecodeError::InvalidPadding),
+        ("QQ", DecodeError::InvalidPadding),
+        ("QUE", DecodeError::InvalidPadding),
+        ("QQ=", DecodeError::InvalidPadding),
+        ("QQ=A", DecodeError::InvalidPadding),
+        ("QQ===", DecodeError::InvalidPadding),
+        ("====", DecodeError::InvalidPadding),
+        ("Q", DecodeError::InvalidLength),
+        ("Q===", DecodeError::InvalidLength),
+        ("Zm9vQU*=", DecodeError::InvalidByte { offset: 6, byte: b'*' }),
+    ];
+
+    let mut buffer = [0u8; 64];
+    for (input, expected) in VALID {
+        let size = STRICT.decode_to(input.as_bytes(), &mut buffer).expect("to decode canonical input");
+        assert_eq!(&buffer[..size], expected.as_bytes());
+    }
+
+    for (input, expected) in INVALID {
+        assert_eq!(STRICT.decode_to(input.as_bytes(), &mut buffer), Err(expected), "input '{}' should fail", input);
+        #[cfg(feature = "alloc")]
+        assert_eq!(STRICT.decode_into_vec(input.as_bytes()), Err(expected), "input '{}' should fail", input);
+    }
+
+    //Lenient mode is unaffected
+    assert_eq!(based64::STANDARD_CODEC.decode_to(b"QR==", &mut buffer), Ok(1));
+    assert_eq!(buffer[0], b'A');
+}
+
+#[test]
+fn should_decode_according_to_padding_policy() {
+    use based64::{Codec, Padding};
+
+    const REQUIRED: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Required);
+    const FORBIDDEN: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden);
+    const STRICT_FORBIDDEN: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden).with_strict(true);
+
+    let mut buffer = [0u8; 64];
+    for codec in [&REQUIRED, &based64::STANDARD_CODEC] {
+        assert_eq!(codec.decode_to(b"Zm9vYg==", &mut buffer), Ok(4));
+        assert_eq!(codec.decode_to(b"Zm9vYmE=", &mut buffer), Ok(5));
+        assert_eq!(codec.decode_to(b"Zm9vYmFy", &mut buffer), Ok(6));
+    }
+    assert_eq!(based64::STANDARD_CODEC.decode_to(b"Zm9vYg", &mut buffer), Ok(4));
+    assert_eq!(REQUIRED.decode_to(b"Zm9vYg", &mut buffer), Err(DecodeError::InvalidPadding));
+    assert_eq!(REQUIRED.decode_to(b"Zm9vYg=", &mut buffer), Err(DecodeError::InvalidPadding));
+    assert_eq!(REQUIRED.decode_to(b"Zm9vYmE==", &mut buffer), Err(DecodeError::InvalidPadding));
+    assert_eq!(REQUIRED.decode_to(b"QQ===", &mut buffer), Err(DecodeError::InvalidPadding));
+    assert_eq!(REQUIRED.decode_to(b"QQ==QQ==", &mut buffer), Err(DecodeError::InvalidPadding));
+    assert_eq!(REQUIRED.with_skip_whitespace(true).decode_to(b"Zm9vYmE= =", &mut buffer), Err(DecodeError::InvalidPadding));
+    assert_eq!(REQUIRED.with_skip_whitespace(true).decode_to(b"Zm9vYmE= ", &mut buffer), Ok(5));
+    assert_eq!(REQUIRED.decode_to(b"Zm9v====", &mut buffer), Err(DecodeError::InvalidPadding));
+
+    for codec in [&FORBIDDEN, &STRICT_FORBIDDEN] {
+        assert_eq!(codec.decode_to(b"Zm9vYg", &mut buffer), Ok(4));
+        assert_eq!(codec.decode_to(b"Zm9vYmE", &mut buffer), Ok(5));
+        assert_eq!(codec.decode_to(b"Zm9vYmFy", &mut buffer), Ok(6));
+        assert_eq!(codec.decode_to(b"Zm9vYg==", &mut buffer), Err(DecodeError::InvalidPadding));
+        assert_eq!(codec.decode_to(b"Zm9vYmE=", &mut buffer), Err(DecodeError::InvalidPadding));
+        assert_eq!(codec.decode_to(b"Zm9v=mFy", &mut buffer), Err(DecodeError::InvalidPadding));
+    }
+    assert_eq!(STRICT_FORBIDDEN.decode_to(b"Zm9vYh", &mut buffer), Err(DecodeError::TrailingBits));
+    assert_eq!(STRICT_FORBIDDEN.decode_to(b"Zm9vY", &mut buffer), Err(DecodeError::InvalidLength));
+}
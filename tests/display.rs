@@ -0,0 +1,77 @@
+use core::fmt::{self, Write};
+
+use based64::{Base64Display, Codec, LineEnding, LineWrap, Padding, STANDARD_CODEC, STANDARD_TABLE, URL_TABLE};
+
+const UNPADDED: Codec<'static> = Codec::new(URL_TABLE).with_padding(Padding::Forbidden);
+const WRAPPED: Codec<'static> = Codec::new(STANDARD_TABLE).with_line_wrap(Some(LineWrap::new(1, LineEnding::CrLf)));
+
+fn encode(codec: &Codec<'_>, input: &[u8]) -> String {
+    let mut encoded = vec![0u8; codec.encode_len(input.len())];
+    assert_eq!(codec.encode_to(input, &mut encoded), Some(encoded.len()));
+    String::from_utf8(encoded).expect("to be ASCII")
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_display_same_as_encode() {
+    for len in (0..100).chain([255, 256, 257, 1000, 4096].iter().copied()) {
+        let mut input = vec![0u8; len];
+        getrandom::getrandom(&mut input).expect("Random should work");
+
+        for codec in [&STANDARD_CODEC, &UNPADDED, &WRAPPED].iter() {
+            let expected = encode(codec, &input);
+            assert_eq!(format!("{}", codec.display(&input)), expected);
+            assert_eq!(format!("{:?}", Base64Display::new(codec, &input)), expected);
+
+            let mut output = String::from("prefix:");
+            assert_eq!(codec.encode_to_fmt(&input, &mut output), Ok(()));
+            assert_eq!(output[7..], expected);
+        }
+    }
+}
+
+#[test]
+fn should_honour_width_and_fill() {
+    let display = STANDARD_CODEC.display(b"foo");
+    assert_eq!(format!("{}", display), "Zm9v");
+    assert_eq!(format!("[{:8}]", display), "[Zm9v    ]");
+    assert_eq!(format!("[{:>8}]", display), "[    Zm9v]");
+    assert_eq!(format!("[{:*^9}]", display), "[**Zm9v***]");
+    assert_eq!(format!("[{:_<6?}]", display), "[Zm9v__]");
+    assert_eq!(format!("[{:2}]", display), "[Zm9v]");
+    assert_eq!(format!("[{:->3}]", STANDARD_CODEC.display(b"")), "[---]");
+}
+
+///Sink with fixed capacity, that fails on overflow.
+struct FixedSink {
+    buffer: [u8; 16],
+    len: usize,
+}
+
+impl Write for FixedSink {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+        self.buffer[self.len..end].copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn should_encode_to_fmt_sink() {
+    let mut sink = FixedSink {
+        buffer: [0; 16],
+        len: 0,
+    };
+    assert_eq!(STANDARD_CODEC.encode_to_fmt(b"foobar", &mut sink), Ok(()));
+    assert_eq!(&sink.buffer[..sink.len], b"Zm9vYmFy");
+    assert_eq!(STANDARD_CODEC.encode_to_fmt(b"foobar", &mut sink), Ok(()));
+    assert_eq!(&sink.buffer[..sink.len], b"Zm9vYmFyZm9vYmFy");
+    assert_eq!(STANDARD_CODEC.encode_to_fmt(b"f", &mut sink), Err(fmt::Error));
+
+    let sink: &mut dyn Write = &mut String::new();
+    assert_eq!(WRAPPED.encode_to_fmt(b"fo", sink), Ok(()));
+}
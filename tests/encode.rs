@@ -1,5 +1,5 @@
-use based64::STANDARD_TABLE;
-use based64::{decode, encode, encode_len, decode_len};
+use based64::{STANDARD_TABLE, STANDARD_CODEC, DecodeError};
+use based64::{decode, encode, encode_len, encode_len_unpadded, decode_len};
 use based64::raw::encode as raw_encode;
 use based64::raw::decode as raw_decode;
 
@@ -43,8 +43,8 @@ fn should_encode_with_default_table() {
 
         assert_eq!(decode_len(base64), input.len(), "decode_len() fails for idx={}", idx);
         let len = match decode(STANDARD_TABLE, base64, &mut decoded) {
-            Some(len) => len,
-            None => panic!("base64 encode fails for idx={}", idx),
+            Ok(len) => len,
+            Err(error) => panic!("base64 decode fails for idx={}: {}", idx, error),
         };
         assert_eq!(len, input.len());
         let decoded = &decoded[..len];
@@ -63,8 +63,8 @@ fn should_encode_with_default_table_vec() {
 
         assert_eq!(decode_len(&base64), input.len(), "decode_len() fails for idx={}", idx);
         let decoded = match based64::vec::decode(STANDARD_TABLE, &base64) {
-            Some(decoded) => decoded,
-            None => panic!("base64 decode fails for idx={}", idx),
+            Ok(decoded) => decoded,
+            Err(error) => panic!("base64 decode fails for idx={}: {}", idx, error),
         };
         assert_eq!(decoded.len(), input.len());
         assert_eq!(decoded, input.as_bytes());
@@ -86,7 +86,7 @@ fn should_raw_encode_with_default_table() {
         let mut len = decoded.len();
         let dst = core::ptr::NonNull::new(decoded.as_mut_ptr()).unwrap();
         assert_eq!(decode_len(base64), input.len(), "decode_len() fails for idx={}", idx);
-        assert!(unsafe { raw_decode(STANDARD_TABLE, base64, dst, &mut len) }, "base64 reverse decode fails for idx={}", idx);
+        assert!(unsafe { raw_decode(STANDARD_TABLE, base64, dst, &mut len) }.is_ok(), "base64 reverse decode fails for idx={}", idx);
         assert_eq!(len, input.len(), "base64 reverse decode has invalid len for idx={}", idx);
         let decoded = &decoded[..len];
         assert_eq!(decoded, input.as_bytes(), "base64 reverse decode wrongly idx={}", idx);
@@ -108,7 +108,7 @@ fn should_raw_encode_with_default_table_vec() {
         let mut len = decoded.len();
         let dst = core::ptr::NonNull::new(decoded.as_mut_ptr()).unwrap();
         assert_eq!(decode_len(base64), input.len(), "decode_len() fails for idx={}", idx);
-        assert!(unsafe { raw_decode(STANDARD_TABLE, base64, dst, &mut len) }, "base64 reverse decode fails for idx={}", idx);
+        assert!(unsafe { raw_decode(STANDARD_TABLE, base64, dst, &mut len) }.is_ok(), "base64 reverse decode fails for idx={}", idx);
         assert_eq!(len, input.len(), "base64 reverse decode has invalid len for idx={}", idx);
         let decoded = &decoded[..len];
         assert_eq!(decoded, input.as_bytes(), "base64 reverse decode wrongly idx={}", idx);
@@ -129,7 +129,7 @@ fn should_raw_encode_all_ascii_with_default_table() {
 
     let mut len = decoded.len();
     let dst = core::ptr::NonNull::new(decoded.as_mut_ptr()).unwrap();
-    assert!(unsafe { raw_decode(STANDARD_TABLE, base64, dst, &mut len) }, "base64 decode fails for all ASCII. Required len={} but len={}", len, decoded.len());
+    assert!(unsafe { raw_decode(STANDARD_TABLE, base64, dst, &mut len) }.is_ok(), "base64 decode fails for all ASCII. Required len={} but len={}", len, decoded.len());
     assert_eq!(len, ALL_ASCII.len());
     let decoded = &decoded[..len];
     assert_eq!(decoded, ALL_ASCII);
@@ -146,6 +146,48 @@ fn should_raw_encode_fail_all_ascii_with_default_table_on_buffer_overflow() {
     }
 }
 
+#[test]
+fn should_raw_decode_fail_all_ascii_with_default_table_on_buffer_overflow() {
+    let mut buffer = [0u8; ALL_ASCII.len()];
+    let dst = core::ptr::NonNull::new(buffer.as_mut_ptr()).unwrap();
+    for idx in 1..ALL_ASCII.len() {
+        let mut len = buffer.len() - idx;
+        let result = unsafe { raw_decode(STANDARD_TABLE, ALL_ASCII_EXPECTED, dst, &mut len) };
+        assert_eq!(result, Err(DecodeError::OutputTooSmall { required: ALL_ASCII.len() }), "base64 decode should fail, but it is successful");
+        assert_eq!(len, ALL_ASCII.len());
+
+        let mut len = buffer.len() - idx;
+        let result = unsafe { STANDARD_CODEC.decode_to_raw(ALL_ASCII_EXPECTED, dst, &mut len) };
+        assert_eq!(result, Err(DecodeError::OutputTooSmall { required: ALL_ASCII.len() }), "base64 decode should fail, but it is successful");
+        assert_eq!(len, ALL_ASCII.len());
+    }
+}
+
+#[test]
+fn should_decode_fail_all_ascii_with_default_table_on_buffer_overflow() {
+    let mut buffer = [0u8; ALL_ASCII.len()];
+    for idx in 1..ALL_ASCII.len() {
+        let dst = &mut buffer[idx..];
+        assert_eq!(decode(STANDARD_TABLE, ALL_ASCII_EXPECTED, dst), Err(DecodeError::OutputTooSmall { required: ALL_ASCII.len() }), "base64 decode should fail, but it is successful");
+        assert_eq!(STANDARD_CODEC.decode_to(ALL_ASCII_EXPECTED, dst), Err(DecodeError::OutputTooSmall { required: ALL_ASCII.len() }), "base64 decode should fail, but it is successful");
+    }
+
+    let mut buffer = [0u8; 1];
+    assert_eq!(decode(STANDARD_TABLE, ALL_ASCII_EXPECTED, &mut buffer), Err(DecodeError::OutputTooSmall { required: ALL_ASCII.len() }));
+}
+
+#[test]
+fn should_decode_unpadded_into_exact_buffer() {
+    for (idx, (input, output)) in SAMPLE_DATA.iter().enumerate() {
+        let unpadded = output.trim_end_matches('=');
+        assert_eq!(decode_len(unpadded.as_bytes()), input.len(), "decode_len() fails for idx={}", idx);
+
+        let mut buffer = vec![0u8; input.len()];
+        assert_eq!(decode(STANDARD_TABLE, unpadded.as_bytes(), &mut buffer), Ok(input.len()), "base64 decode fails for idx={}", idx);
+        assert_eq!(buffer, input.as_bytes());
+    }
+}
+
 #[test]
 fn should_encode_fail_all_ascii_with_default_table_on_buffer_overflow() {
     let mut buffer = [0u8; ALL_ASCII_EXPECTED.len()];
@@ -165,7 +207,7 @@ fn verify_encode_safety() {
         let dst = &mut out_buffer[..encode_len(src.len())];
         getrandom::getrandom(src).expect("Random should work for fuck sake");
         assert!(encode(STANDARD_TABLE, src, dst).is_some(), "ENCODE SHOULD NOT FAIL");
-        assert!(decode(STANDARD_TABLE, dst, src).is_some(), "DECODE SHOULD NOT FAIL");
+        assert!(decode(STANDARD_TABLE, dst, src).is_ok(), "DECODE SHOULD NOT FAIL");
     }
 }
 
@@ -184,3 +226,38 @@ fn verify_encode_to_vec_safety() {
         assert_eq!(based64::string::encode(STANDARD_TABLE, src), base64_string);
     }
 }
+
+#[test]
+fn should_encode_according_to_padding_policy() {
+    const UNPADDED: based64::Codec<'static> = based64::Codec::new(STANDARD_TABLE).with_padding(based64::Padding::Forbidden);
+    const PADDED: based64::Codec<'static> = based64::Codec::new(STANDARD_TABLE).with_padding(based64::Padding::Required);
+
+    let mut buffer = [0u8; 4096];
+    let mut decoded = [0u8; 4096];
+    for (idx, (input, output)) in SAMPLE_DATA.iter().enumerate() {
+        let unpadded = output.trim_end_matches('=');
+        assert_eq!(encode_len_unpadded(input.len()), unpadded.len(), "encode_len_unpadded() fails for idx={}", idx);
+        assert_eq!(UNPADDED.encode_len(input.len()), unpadded.len(), "encode_len() fails for idx={}", idx);
+        assert_eq!(PADDED.encode_len(input.len()), output.len(), "encode_len() fails for idx={}", idx);
+
+        let len = UNPADDED.encode_to(input.as_bytes(), &mut buffer).expect("to encode");
+        assert_eq!(&buffer[..len], unpadded.as_bytes());
+        let len = UNPADDED.decode_to(unpadded.as_bytes(), &mut decoded).expect("to decode");
+        assert_eq!(&decoded[..len], input.as_bytes());
+        if !unpadded.is_empty() {
+            assert!(UNPADDED.encode_to(input.as_bytes(), &mut buffer[..unpadded.len() - 1]).is_none());
+        }
+
+        let len = PADDED.encode_to(input.as_bytes(), &mut buffer).expect("to encode");
+        assert_eq!(&buffer[..len], output.as_bytes());
+        let len = PADDED.decode_to(output.as_bytes(), &mut decoded).expect("to decode");
+        assert_eq!(&decoded[..len], input.as_bytes());
+
+        #[cfg(feature = "alloc")]
+        {
+            assert_eq!(UNPADDED.encode_into_vec(input.as_bytes()), unpadded.as_bytes());
+            assert_eq!(UNPADDED.encode_into_string(input.as_bytes()), unpadded);
+            assert_eq!(PADDED.encode_into_string(input.as_bytes()), *output);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, Read, Write};
+
+use based64::io::{DecoderReader, EncoderWriter};
+use based64::{Codec, DecodeError, Padding, STANDARD_CODEC, STANDARD_TABLE};
+
+///Writer accepting at most `limit` bytes per call
+struct ShortWriter {
+    data: Vec<u8>,
+    limit: usize,
+    interrupt: bool,
+}
+
+impl Write for ShortWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let len = core::cmp::min(buf.len(), self.limit);
+        self.data.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+///Reader returning at most `limit` bytes per call
+struct ShortReader<'a> {
+    data: &'a [u8],
+    limit: usize,
+    interrupt: bool,
+}
+
+impl Read for ShortReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        let len = core::cmp::min(core::cmp::min(buf.len(), self.limit), self.data.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Ok(len)
+    }
+}
+
+fn read_all<R: Read>(mut reader: R, fragment: usize) -> io::Result<Vec<u8>> {
+    let mut result = Vec::new();
+    let mut buffer = vec![0u8; fragment];
+    loop {
+        match reader.read(&mut buffer)? {
+            0 => break Ok(result),
+            size => result.extend_from_slice(&buffer[..size]),
+        }
+    }
+}
+
+fn decode_error(error: io::Error) -> DecodeError {
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    *error.into_inner().expect("to have inner error").downcast::<DecodeError>().expect("to be DecodeError")
+}
+
+fn random_input(len: usize) -> Vec<u8> {
+    let mut input = vec![0u8; len];
+    getrandom::getrandom(&mut input).expect("Random should work");
+    input
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_encode_stream_in_fragments() {
+    const UNPADDED: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden);
+
+    for codec in [&STANDARD_CODEC, &UNPADDED] {
+        for len in [0, 1, 2, 3, 4, 5, 767, 768, 769, 770, 5000] {
+            let input = random_input(len);
+            let expected = codec.encode_into_vec(&input);
+
+            for fragment in [1, 2, 3, 4, 7, 766, 767, 768, 1000, 10000] {
+                let mut encoder = EncoderWriter::new(codec, Vec::new());
+                for chunk in input.chunks(fragment) {
+                    encoder.write_all(chunk).expect("to write");
+                }
+                let output = encoder.finish().expect("to finish");
+                assert_eq!(output, expected, "mismatch for len={} fragment={}", len, fragment);
+            }
+
+            let mut encoder = EncoderWriter::new(codec, ShortWriter { data: Vec::new(), limit: 5, interrupt: false });
+            encoder.write_all(&input).expect("to write");
+            encoder.flush().expect("to flush");
+            let output = encoder.finish().expect("to finish");
+            assert_eq!(output.data, expected, "mismatch for len={} with short writes", len);
+        }
+    }
+}
+
+#[test]
+fn should_propagate_write_error() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut encoder = EncoderWriter::new(&STANDARD_CODEC, FailingWriter);
+    //Data is buffered, error is reported once it needs to be written
+    assert_eq!(encoder.write(b"foobar").expect("to buffer"), 6);
+    assert_eq!(encoder.flush().unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(encoder.write(b"foobar").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(encoder.finish().err().map(|error| error.kind()), Some(io::ErrorKind::BrokenPipe));
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_decode_stream_in_fragments() {
+    const STRICT: Codec<'static> = Codec::new(STANDARD_TABLE).with_strict(true);
+    const UNPADDED: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden);
+
+    for codec in [&STANDARD_CODEC, &STRICT, &UNPADDED] {
+        for len in [0, 1, 2, 3, 4, 5, 767, 768, 769, 770, 5000] {
+            let input = random_input(len);
+            let encoded = codec.encode_into_vec(&input);
+
+            for fragment in [1, 2, 3, 4, 7, 766, 767, 768, 1000, 10000] {
+                let decoder = DecoderReader::new(codec, encoded.as_slice());
+                let output = read_all(decoder, fragment).expect("to decode");
+                assert_eq!(output, input, "mismatch for len={} fragment={}", len, fragment);
+            }
+
+            for limit in [1, 3, 5, 1025] {
+                let decoder = DecoderReader::new(codec, ShortReader { data: &encoded, limit, interrupt: false });
+                let output = read_all(decoder, 100).expect("to decode");
+                assert_eq!(output, input, "mismatch for len={} with short reads of {}", len, limit);
+            }
+        }
+    }
+}
+
+#[test]
+fn should_decode_stream_padding() {
+    const STRICT: Codec<'static> = Codec::new(STANDARD_TABLE).with_strict(true);
+
+    let mut output = Vec::new();
+    DecoderReader::new(&STANDARD_CODEC, &b"Zm9vYg==Zm9v"[..]).read_to_end(&mut output).expect("to decode");
+    assert_eq!(output, b"foob");
+
+    let error = DecoderReader::new(&STRICT, &b"Zm9vYg==Zm9v"[..]).read_to_end(&mut Vec::new()).unwrap_err();
+    assert_eq!(decode_error(error), DecodeError::InvalidPadding);
+
+    let error = DecoderReader::new(&STRICT, &b"Zm9vYg="[..]).read_to_end(&mut Vec::new()).unwrap_err();
+    assert_eq!(decode_error(error), DecodeError::InvalidPadding);
+
+    let mut output = Vec::new();
+    DecoderReader::new(&STRICT, &b"Zm9vYg=="[..]).read_to_end(&mut output).expect("to decode");
+    assert_eq!(output, b"foob");
+}
+
+#[test]
+fn should_report_stream_error_offset() {
+    let mut encoded = STANDARD_CODEC.encode_into_vec(&[0u8; 3000]);
+    encoded[2500] = b'*';
+
+    for limit in [1, 7, 4000] {
+        let decoder = DecoderReader::new(&STANDARD_CODEC, ShortReader { data: &encoded, limit, interrupt: false });
+        let error = read_all(decoder, 10).unwrap_err();
+        assert_eq!(decode_error(error), DecodeError::InvalidByte { offset: 2500, byte: b'*' });
+    }
+}
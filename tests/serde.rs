@@ -0,0 +1,80 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+
+///Stands for `bytes::Bytes`-like types.
+#[derive(Debug, PartialEq, Eq)]
+struct Blob(Vec<u8>);
+
+impl AsRef<[u8]> for Blob {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Blob {
+    fn from(data: Vec<u8>) -> Self {
+        Self(data)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Payload {
+    #[serde(with = "based64::serde::standard")]
+    data: Vec<u8>,
+    #[serde(with = "based64::serde::url_safe")]
+    key: [u8; 4],
+    #[serde(with = "based64::serde::standard::option")]
+    optional: Option<Vec<u8>>,
+    #[serde(with = "based64::serde::url_safe::option", default)]
+    missing: Option<[u8; 2]>,
+    #[serde(with = "based64::serde::standard")]
+    blob: Blob,
+}
+
+#[test]
+fn should_serialize_and_deserialize_fields() {
+    let payload = Payload {
+        data: b"foobar".to_vec(),
+        key: [0xfb, 0xff, 0xbf, 0xfb],
+        optional: Some(b"foob".to_vec()),
+        missing: None,
+        blob: Blob(b"fo".to_vec()),
+    };
+
+    let json = serde_json::to_string(&payload).expect("to serialize");
+    assert_eq!(json, r#"{"data":"Zm9vYmFy","key":"-_-_-w==","optional":"Zm9vYg==","missing":null,"blob":"Zm8="}"#);
+    assert_eq!(serde_json::from_str::<Payload>(&json).expect("to deserialize"), payload);
+
+    let json = r#"{"data":"","key":"-_-_-w","optional":null,"blob":"Zm8"}"#;
+    let payload = serde_json::from_str::<Payload>(json).expect("to deserialize");
+    assert_eq!(payload.data, b"");
+    assert_eq!(payload.key, [0xfb, 0xff, 0xbf, 0xfb]);
+    assert_eq!(payload.optional, None);
+    assert_eq!(payload.missing, None);
+    assert_eq!(payload.blob, Blob(b"fo".to_vec()));
+}
+
+#[test]
+fn should_deserialize_escaped_string() {
+    #[derive(Deserialize)]
+    struct Data(#[serde(with = "based64::serde::standard")] Vec<u8>);
+
+    let data = serde_json::from_str::<Data>(r#""Zm9v\u0059mFy""#).expect("to deserialize");
+    assert_eq!(data.0, b"foobar");
+}
+
+#[test]
+fn should_report_descriptive_errors() {
+    let json = r#"{"data":"Zm9*","key":"-_-_-w","optional":null,"blob":""}"#;
+    let error = serde_json::from_str::<Payload>(json).expect_err("to fail").to_string();
+    assert!(error.starts_with("invalid BASE64: Invalid byte 0x2a at offset 3"), "{}", error);
+
+    let json = r#"{"data":"","key":"Zm9v","optional":null,"blob":""}"#;
+    let error = serde_json::from_str::<Payload>(json).expect_err("to fail").to_string();
+    assert!(error.starts_with("invalid length 3, expected BASE64 string of different length"), "{}", error);
+
+    let json = r#"{"data":[1, 2],"key":"-_-_-w","optional":null,"blob":""}"#;
+    let error = serde_json::from_str::<Payload>(json).expect_err("to fail").to_string();
+    assert!(error.starts_with("invalid type: sequence, expected BASE64 string"), "{}", error);
+}
@@ -0,0 +1,135 @@
+use based64::stream::{Decoder, Encoder};
+use based64::{Codec, DecodeError, LineEnding, LineWrap, Padding, STANDARD_CODEC, STANDARD_TABLE};
+
+const STRICT: Codec<'static> = Codec::new(STANDARD_TABLE).with_strict(true);
+const UNPADDED: Codec<'static> = Codec::new(STANDARD_TABLE).with_padding(Padding::Forbidden);
+const WRAPPED: Codec<'static> = Codec::new(STANDARD_TABLE).with_line_wrap(Some(LineWrap::new(5, LineEnding::CrLf)));
+
+fn random_input(len: usize) -> Vec<u8> {
+    let mut input = vec![0u8; len];
+    getrandom::getrandom(&mut input).expect("Random should work");
+    input
+}
+
+fn encode_all(codec: &Codec<'_>, input: &[u8]) -> Vec<u8> {
+    let mut output = vec![0u8; codec.encode_len(input.len())];
+    assert_eq!(codec.encode_to(input, &mut output), Some(output.len()));
+    output
+}
+
+fn encode_fragments(codec: &Codec<'_>, input: &[u8], fragment: usize, output_size: usize) -> Vec<u8> {
+    let mut encoder = Encoder::new(codec);
+    let mut result = Vec::new();
+    let mut output = vec![0u8; output_size];
+
+    for mut chunk in input.chunks(fragment) {
+        while !chunk.is_empty() {
+            let (consumed, written) = encoder.push(chunk, &mut output);
+            assert!(consumed > 0 || written > 0, "no progress");
+            result.extend_from_slice(&output[..written]);
+            chunk = &chunk[consumed..];
+        }
+    }
+
+    let written = encoder.finish(&mut output).expect("to finish");
+    result.extend_from_slice(&output[..written]);
+    result
+}
+
+fn decode_fragments(codec: &Codec<'_>, input: &[u8], fragment: usize, output_size: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut decoder = Decoder::new(codec);
+    let mut result = Vec::new();
+    let mut output = vec![0u8; output_size];
+
+    for mut chunk in input.chunks(fragment) {
+        while !chunk.is_empty() {
+            let (consumed, written) = decoder.push(chunk, &mut output)?;
+            assert!(consumed > 0 || written > 0, "no progress");
+            result.extend_from_slice(&output[..written]);
+            chunk = &chunk[consumed..];
+        }
+    }
+
+    let written = decoder.finish(&mut output)?;
+    result.extend_from_slice(&output[..written]);
+    Ok(result)
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_encode_and_decode_in_fragments() {
+    //Wrapped output might need room for line ending before group
+    for (codec, min_output) in [(&STANDARD_CODEC, 4), (&STRICT, 4), (&UNPADDED, 4), (&WRAPPED, 6)] {
+        for len in [0, 1, 2, 3, 4, 5, 100, 1000] {
+            let input = random_input(len);
+            let expected = encode_all(codec, &input);
+
+            for fragment in [1, 2, 3, 4, 5, 7, 64, 2000] {
+                for output_size in [4, 5, 6, 7, 13, 100, 2000].iter().copied().filter(|size| *size >= min_output) {
+                    let encoded = encode_fragments(codec, &input, fragment, output_size);
+                    assert_eq!(encoded, expected, "encode mismatch for len={} fragment={} output={}", len, fragment, output_size);
+
+                    let decoded = decode_fragments(codec, &encoded, fragment, output_size).expect("to decode");
+                    assert_eq!(decoded, input, "decode mismatch for len={} fragment={} output={}", len, fragment, output_size);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn should_not_progress_without_output() {
+    let mut encoder = Encoder::new(&STANDARD_CODEC);
+    assert_eq!(encoder.push(b"fo", &mut []), (2, 0));
+    assert_eq!(encoder.push(b"obar", &mut [0u8; 3]), (0, 0));
+    assert_eq!(encoder.finish(&mut [0u8; 3]), None);
+    let mut output = [0u8; 4];
+    assert_eq!(encoder.finish(&mut output), Some(4));
+    assert_eq!(&output, b"Zm8=");
+
+    let mut decoder = Decoder::new(&STANDARD_CODEC);
+    assert_eq!(decoder.push(b"Zm9vYmFy", &mut [0u8; 2]), Ok((4, 0)));
+    assert_eq!(decoder.push(b"YmFy", &mut [0u8; 2]), Ok((0, 0)));
+    let mut output = [0u8; 6];
+    assert_eq!(decoder.push(b"YmFy", &mut output), Ok((4, 6)));
+    assert_eq!(&output, b"foobar");
+
+    assert_eq!(decoder.push(b"Zg", &mut output), Ok((2, 0)));
+    assert_eq!(decoder.finish(&mut []), Err(DecodeError::OutputTooSmall { required: 1 }));
+    assert_eq!(decoder.finish(&mut output), Ok(1));
+    assert_eq!(output[0], b'f');
+}
+
+#[test]
+fn should_stop_at_padding() {
+    let mut output = [0u8; 16];
+
+    let mut decoder = Decoder::new(&STANDARD_CODEC);
+    assert_eq!(decoder.push(b"Zm9vYg==", &mut output), Ok((8, 4)));
+    assert_eq!(decoder.push(b"Zm9v", &mut output), Ok((4, 0)));
+    assert_eq!(decoder.finish(&mut output), Ok(0));
+
+    let mut decoder = Decoder::new(&STRICT);
+    assert_eq!(decoder.push(b"Zm9vYg==", &mut output), Ok((8, 4)));
+    assert_eq!(decoder.push(b"", &mut output), Ok((0, 0)));
+    assert_eq!(decoder.push(b"Z", &mut output), Err(DecodeError::InvalidPadding));
+
+    let mut decoder = Decoder::new(&UNPADDED);
+    assert_eq!(decoder.push(b"Zm9vYg==", &mut output), Err(DecodeError::InvalidPadding));
+}
+
+#[test]
+fn should_report_error_offset() {
+    let mut encoded = encode_all(&STANDARD_CODEC, &[0u8; 300]);
+    encoded[250] = b'*';
+
+    for fragment in [1, 3, 4, 1000] {
+        for output_size in [3, 100, 1000] {
+            let result = decode_fragments(&STANDARD_CODEC, &encoded, fragment, output_size);
+            assert_eq!(result, Err(DecodeError::InvalidByte { offset: 250, byte: b'*' }), "fragment={} output={}", fragment, output_size);
+        }
+    }
+
+    let result = decode_fragments(&STRICT, b"Zm9vYmFyZ", 2, 10);
+    assert_eq!(result, Err(DecodeError::InvalidLength));
+}
@@ -0,0 +1,91 @@
+use based64::stream::Decoder;
+use based64::{Codec, DecodeError, STANDARD_CODEC, STANDARD_TABLE};
+
+const LENIENT: Codec<'static> = Codec::new(STANDARD_TABLE).with_skip_whitespace(true);
+const STRICT: Codec<'static> = Codec::new(STANDARD_TABLE).with_skip_whitespace(true).with_strict(true);
+
+const WHITESPACE: [u8; 5] = [b' ', b'\t', b'\n', b'\x0C', b'\r'];
+
+fn random_input(len: usize) -> Vec<u8> {
+    let mut input = vec![0u8; len];
+    getrandom::getrandom(&mut input).expect("Random should work");
+    input
+}
+
+fn encode(input: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![0u8; based64::encode_len(input.len())];
+    assert_eq!(STANDARD_CODEC.encode_to(input, &mut encoded), Some(encoded.len()));
+    encoded
+}
+
+///Inserts whitespace before every character, using random bytes to choose how much.
+fn insert_whitespace(encoded: &[u8]) -> Vec<u8> {
+    let noise = random_input(encoded.len() + 1);
+    let mut result = Vec::new();
+    for (ch, noise) in encoded.iter().chain(core::iter::once(&b'\n')).zip(noise) {
+        for idx in 0..noise % 3 {
+            result.push(WHITESPACE[(noise as usize + idx as usize) % WHITESPACE.len()]);
+        }
+        result.push(*ch);
+    }
+    result
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_skip_whitespace() {
+    for len in 0..200 {
+        let input = random_input(len);
+        let encoded = insert_whitespace(&encode(&input));
+
+        for codec in [&LENIENT, &STRICT] {
+            assert_eq!(codec.decode_len(&encoded), len);
+            //Exact buffer
+            let mut decoded = vec![0u8; len];
+            assert_eq!(codec.decode_to(&encoded, &mut decoded), Ok(len), "decode fails for {:?}", String::from_utf8_lossy(&encoded));
+            assert_eq!(decoded, input);
+
+            //Buffer for whitespace as well
+            let mut decoded = vec![0u8; based64::decode_len(&encoded)];
+            assert_eq!(codec.decode_to(&encoded, &mut decoded), Ok(len));
+            assert_eq!(&decoded[..len], &input[..]);
+        }
+    }
+}
+
+#[test]
+fn should_report_original_offset() {
+    let mut output = [0u8; 16];
+    assert_eq!(LENIENT.decode_to(b"  Zm 9v\tYm*h", &mut output), Err(DecodeError::InvalidByte { offset: 10, byte: b'*' }));
+    assert_eq!(STRICT.decode_to(b"Zm9v\r\n\r\nY*Fy", &mut output), Err(DecodeError::InvalidByte { offset: 9, byte: b'*' }));
+    assert_eq!(STRICT.decode_to(b"Zm9vYmF*", &mut output), Err(DecodeError::InvalidByte { offset: 7, byte: b'*' }));
+    //Vertical tab is not whitespace according to `u8::is_ascii_whitespace`
+    assert_eq!(LENIENT.decode_to(b"Zm9v\x0BYmFy", &mut output), Err(DecodeError::InvalidByte { offset: 4, byte: b'\x0B' }));
+    assert_eq!(STANDARD_CODEC.decode_to(b"Zm9v YmFy", &mut output), Err(DecodeError::InvalidByte { offset: 4, byte: b' ' }));
+}
+
+#[test]
+fn should_check_padding_around_whitespace() {
+    let mut output = [0u8; 16];
+    assert_eq!(STRICT.decode_to(b"Zm9vYg= =\n", &mut output), Ok(4));
+    assert_eq!(&output[..4], b"foob");
+    assert_eq!(STRICT.decode_to(b"Zm9vYg== Zg==", &mut output), Err(DecodeError::InvalidPadding));
+    assert_eq!(STRICT.decode_to(b"Zm9vY g=", &mut output), Err(DecodeError::InvalidPadding));
+    assert_eq!(LENIENT.decode_to(b"Zm9vYg== Zg==", &mut output), Ok(4));
+    assert_eq!(LENIENT.decode_to(b"Zm9v Yg", &mut output), Ok(4));
+}
+
+#[test]
+fn should_skip_whitespace_in_stream() {
+    let encoded = b" Zm9v\r\n YmFy \t";
+    let mut decoder = Decoder::new(&LENIENT);
+    let mut output = [0u8; 6];
+    let mut written = 0;
+    for chunk in encoded.chunks(3) {
+        let (consumed, size) = decoder.push(chunk, &mut output[written..]).expect("to decode");
+        assert_eq!(consumed, chunk.len());
+        written += size;
+    }
+    written += decoder.finish(&mut output[written..]).expect("to finish");
+    assert_eq!(&output[..written], b"foobar");
+}
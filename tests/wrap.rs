@@ -0,0 +1,72 @@
+#![cfg(feature = "alloc")]
+
+use based64::{Codec, DecodeError, LineEnding, LineWrap, STANDARD_CODEC, STANDARD_TABLE};
+
+const MIME: Codec<'static> = Codec::new(STANDARD_TABLE).with_line_wrap(Some(LineWrap::MIME));
+const PEM: Codec<'static> = Codec::new(STANDARD_TABLE).with_line_wrap(Some(LineWrap::PEM));
+
+fn random_input(len: usize) -> Vec<u8> {
+    let mut input = vec![0u8; len];
+    getrandom::getrandom(&mut input).expect("Random should work");
+    input
+}
+
+fn reference_wrap(encoded: &[u8], line_wrap: LineWrap) -> Vec<u8> {
+    let mut result = Vec::new();
+    for (idx, line) in encoded.chunks(line_wrap.width()).enumerate() {
+        if idx > 0 {
+            result.extend_from_slice(line_wrap.ending().as_bytes());
+        }
+        result.extend_from_slice(line);
+    }
+    result
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_wrap_lines() {
+    for line_wrap in [LineWrap::MIME, LineWrap::PEM, LineWrap::new(1, LineEnding::CrLf), LineWrap::new(5, LineEnding::Lf), LineWrap::new(8, LineEnding::CrLf)] {
+        let codec = STANDARD_CODEC.with_line_wrap(Some(line_wrap));
+        for len in 0..300 {
+            let input = random_input(len);
+            let expected = reference_wrap(&STANDARD_CODEC.encode_into_vec(&input), line_wrap);
+            assert_eq!(codec.encode_len(len), expected.len());
+            assert_eq!(based64::encode_len_wrapped(len, line_wrap), expected.len());
+
+            let mut encoded = vec![0u8; expected.len()];
+            assert_eq!(codec.encode_to(&input, &mut encoded), Some(expected.len()));
+            assert_eq!(encoded, expected, "mismatch for len={} wrap={:?}", len, line_wrap);
+            assert_eq!(codec.encode_into_vec(&input), expected);
+            assert_eq!(codec.encode_into_string(&input).as_bytes(), &expected[..]);
+
+            assert_eq!(codec.decode_len(&encoded), len);
+            let mut decoded = vec![0u8; len];
+            assert_eq!(codec.decode_to(&encoded, &mut decoded), Ok(len), "decode fails for len={} wrap={:?}", len, line_wrap);
+            assert_eq!(decoded, input);
+            assert_eq!(codec.with_strict(true).decode_into_vec(&encoded), Ok(input));
+        }
+    }
+}
+
+#[test]
+fn should_wrap_mime_and_pem() {
+    let input = [0u8; 60];
+    let mime = MIME.encode_into_string(&input);
+    assert_eq!(mime, format!("{}\r\n{}", "A".repeat(76), "AAAA"));
+    let pem = PEM.encode_into_string(&input);
+    assert_eq!(pem, format!("{}\n{}", "A".repeat(64), "AAAAAAAAAAAAAAAA"));
+}
+
+#[test]
+fn should_skip_line_breaks_on_decode() {
+    assert_eq!(MIME.decode_into_vec(b"Zm\r\n9v\nYm\rE=\r\n"), Ok(b"fooba".to_vec()));
+    assert_eq!(MIME.with_strict(true).decode_into_vec(b"\nZm9vYmE=\r\n"), Ok(b"fooba".to_vec()));
+    assert_eq!(MIME.with_strict(true).decode_into_vec(b"Zm9vYmE=\r\nZg"), Err(DecodeError::InvalidPadding));
+    assert_eq!(MIME.decode_into_vec(b"Zm9v\r\nY*E="), Err(DecodeError::InvalidByte { offset: 7, byte: b'*' }));
+    assert_eq!(MIME.decode_into_vec(b"Zm\r\n9*"), Err(DecodeError::InvalidByte { offset: 5, byte: b'*' }));
+    //Codec without line wrapping treats line breaks as invalid input
+    assert_eq!(STANDARD_CODEC.decode_into_vec(b"Zm9v\r\nYmE="), Err(DecodeError::InvalidByte { offset: 4, byte: b'\r' }));
+
+    let mut output = [0u8; 4];
+    assert_eq!(MIME.decode_to(b"Zm9v\r\nYmE=", &mut output), Err(DecodeError::OutputTooSmall { required: 5 }));
+}
@@ -0,0 +1,110 @@
+use based64::DecodeError;
+use based64::z85;
+
+const HELLO: [u8; 8] = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+const CLIENT_PUBLIC: [u8; 32] = [
+    0xbb, 0x88, 0x47, 0x1d, 0x65, 0xe2, 0x65, 0x9b, 0x30, 0xc5, 0x5a, 0x53, 0x21, 0xce, 0xbb, 0x5a,
+    0xab, 0x2b, 0x70, 0xa3, 0x98, 0x64, 0x5c, 0x26, 0xdc, 0xa2, 0xb2, 0xfc, 0xb4, 0x3f, 0xc5, 0x18,
+];
+const CLIENT_PUBLIC_ENCODED: &[u8; 40] = b"Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID";
+const ENCODED_KEY: [u8; 40] = z85::encode_key(&CLIENT_PUBLIC);
+
+#[test]
+fn should_encode_rfc32_vectors() {
+    let mut output = [0u8; 10];
+    assert_eq!(z85::encode_to(&HELLO, &mut output), Some(10));
+    assert_eq!(&output, b"HelloWorld");
+
+    let mut output = [0u8; 8];
+    assert_eq!(z85::decode_to(b"HelloWorld", &mut output), Ok(8));
+    assert_eq!(output, HELLO);
+
+    assert_eq!(z85::encode_len(8), Some(10));
+    assert_eq!(z85::decode_len(b"HelloWorld"), Some(8));
+}
+
+#[test]
+fn should_encode_curve_key() {
+    assert_eq!(&ENCODED_KEY, CLIENT_PUBLIC_ENCODED);
+    assert_eq!(z85::decode_key(CLIENT_PUBLIC_ENCODED), Ok(CLIENT_PUBLIC));
+    assert_eq!(z85::decode_key(&CLIENT_PUBLIC_ENCODED[..35]), Err(DecodeError::InvalidLength));
+
+    let mut invalid = *CLIENT_PUBLIC_ENCODED;
+    invalid[17] = b'~';
+    assert_eq!(z85::decode_key(&invalid), Err(DecodeError::InvalidByte {
+        offset: 17,
+        byte: b'~',
+    }));
+}
+
+#[test]
+fn should_require_multiple_of_4() {
+    let mut output = [0u8; 16];
+    assert_eq!(z85::encode_len(3), None);
+    assert_eq!(z85::encode_to(b"foo", &mut output), None);
+    assert_eq!(z85::encode_to(&HELLO, &mut output[..9]), None);
+    assert_eq!(z85::decode_len(b"Hello"), Some(4));
+    assert_eq!(z85::decode_len(b"Hell"), None);
+    assert_eq!(z85::decode_to(b"Hell", &mut output), Err(DecodeError::InvalidLength));
+    assert_eq!(z85::decode_to(b"HelloWorld", &mut output[..7]), Err(DecodeError::OutputTooSmall {
+        required: 8,
+    }));
+    assert_eq!(z85::decode_to(b"Hello Worl", &mut output), Err(DecodeError::InvalidByte {
+        offset: 5,
+        byte: b' ',
+    }));
+    assert_eq!(z85::decode_to(b"%nSc1", &mut output), Err(DecodeError::InvalidByte {
+        offset: 4,
+        byte: b'1',
+    }));
+    assert_eq!(z85::decode_to(b"%nSc0", &mut output), Ok(4));
+    assert_eq!(output[..4], [0xff; 4]);
+}
+
+#[cfg_attr(miri, ignore)] // too slow
+#[test]
+fn should_roundtrip_padded() {
+    for len in 0..200 {
+        let mut input = vec![0u8; len];
+        getrandom::getrandom(&mut input).expect("Random should work");
+
+        let mut encoded = vec![0u8; z85::encode_padded_len(len)];
+        assert_eq!(z85::encode_padded_to(&input, &mut encoded), Some(encoded.len()));
+        if len % 4 == 0 {
+            let mut unpadded = vec![0u8; z85::encode_len(len).expect("multiple of 4")];
+            assert_eq!(z85::encode_to(&input, &mut unpadded), Some(unpadded.len()));
+            assert_eq!(unpadded, encoded);
+        }
+
+        let mut decoded = vec![0u8; z85::decode_padded_len(&encoded).expect("valid length")];
+        assert_eq!(decoded.len(), len);
+        assert_eq!(z85::decode_padded_to(&encoded, &mut decoded), Ok(len));
+        assert_eq!(decoded, input);
+    }
+}
+
+#[test]
+fn should_decode_padded() {
+    let mut output = [0u8; 8];
+    assert_eq!(z85::encode_padded_to(b"\x86\x4f\xd2", &mut output), Some(4));
+    assert_eq!(&output[..4], b"Helj");
+    assert_eq!(z85::decode_padded_to(b"Helj", &mut output), Ok(3));
+    assert_eq!(output[..3], HELLO[..3]);
+    assert_eq!(z85::decode_padded_len(b"HelloW"), None);
+    assert_eq!(z85::decode_padded_to(b"HelloW", &mut output), Err(DecodeError::InvalidLength));
+    assert_eq!(z85::decode_padded_to(b"%nSd", &mut output), Err(DecodeError::InvalidByte {
+        offset: 3,
+        byte: b'd',
+    }));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn should_encode_and_decode_vec() {
+    assert_eq!(z85::encode_into_string(&HELLO).as_deref(), Some("HelloWorld"));
+    assert_eq!(z85::encode_into_string(b"foo"), None);
+    assert_eq!(z85::decode_into_vec(b"HelloWorld"), Ok(HELLO.to_vec()));
+    assert_eq!(z85::decode_into_vec(b"Hello!"), Err(DecodeError::InvalidLength));
+    assert_eq!(z85::encode_padded_into_string(b"\x86\x4f\xd2"), "Helj");
+    assert_eq!(z85::decode_padded_into_vec(b"Helj"), Ok(HELLO[..3].to_vec()));
+}